@@ -10,7 +10,10 @@ fn main() {
 
 fn setup_2d(mut commands: Commands) {
     commands.spawn(Camera2dBundle {
-        camera: Camera { order: 1, ..Default::default() },
+        camera: Camera {
+            order: 1,
+            ..Default::default()
+        },
         ..Default::default()
     });
     commands.spawn(SpriteBundle {
@@ -1,5 +1,10 @@
+use std::any::TypeId;
+
 use bevy::{prelude::*, utils::HashMap};
-use bevy_editor_pls_core::{editor_window::EditorWindow, Editor, EditorEvent};
+use bevy_editor_pls_core::{
+    editor::EditorInternalState, editor_window::EditorWindow, Editor, EditorEvent, Selection,
+    ShortcutHints, UndoStack,
+};
 
 #[derive(Debug)]
 pub enum Button {
@@ -130,11 +135,20 @@ impl Binding {
     }
 }
 
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Action {
     PlayPauseEditor,
     PauseUnpauseTime,
     FocusSelected,
+    DeleteSelected,
+    /// Reverts the last edit recorded in the [`UndoStack`]
+    Undo,
+    Redo,
+    /// Focuses the window, docking it if it isn't open yet. Create with [`Action::open_window`].
+    OpenWindow {
+        name: &'static str,
+        window: TypeId,
+    },
 
     // maybe investigate [GizmoOptions].hotkeys
     // https://docs.rs/transform-gizmo-bevy/latest/transform_gizmo_bevy/struct.GizmoHotkeys.html
@@ -152,6 +166,10 @@ impl std::fmt::Display for Action {
             Action::PlayPauseEditor => write!(f, "Play/Pause editor"),
             Action::PauseUnpauseTime => write!(f, "Pause/Unpause time"),
             Action::FocusSelected => write!(f, "Focus Selected Entity"),
+            Action::DeleteSelected => write!(f, "Delete Selected Entities"),
+            Action::Undo => write!(f, "Undo"),
+            Action::Redo => write!(f, "Redo"),
+            Action::OpenWindow { name, .. } => write!(f, "Open {} window", name),
             #[cfg(feature = "default_windows")]
             Action::SetGizmoModeTranslate => write!(f, "Activate translation gizmo"),
             #[cfg(feature = "default_windows")]
@@ -162,7 +180,35 @@ impl std::fmt::Display for Action {
     }
 }
 
-/// Resource mapping input bindings to [`Action`]s
+impl Action {
+    pub fn open_window<W: EditorWindow>() -> Self {
+        Action::OpenWindow {
+            name: W::NAME,
+            window: TypeId::of::<W>(),
+        }
+    }
+
+    /// Every action without parameters, in the order they are listed in the [`ControlsWindow`]
+    pub const ALL: &'static [Action] = &[
+        Action::PlayPauseEditor,
+        Action::PauseUnpauseTime,
+        Action::FocusSelected,
+        Action::DeleteSelected,
+        Action::Undo,
+        Action::Redo,
+        #[cfg(feature = "default_windows")]
+        Action::SetGizmoModeTranslate,
+        #[cfg(feature = "default_windows")]
+        Action::SetGizmoModeRotate,
+        #[cfg(feature = "default_windows")]
+        Action::SetGizmoModeScale,
+    ];
+}
+
+/// Resource mapping input bindings to [`Action`]s.
+///
+/// Insert your own before adding the [`EditorPlugin`](crate::EditorPlugin) to replace the
+/// [default bindings](EditorControls::default_bindings).
 #[derive(Resource, Default)]
 pub struct EditorControls {
     pub actions: HashMap<Action, Vec<Binding>>,
@@ -172,9 +218,14 @@ impl EditorControls {
     pub fn insert(&mut self, action: Action, binding: Binding) {
         self.actions.entry(action).or_default().push(binding);
     }
-    fn get(&self, action: &Action) -> &[Binding] {
+    /// The bindings currently triggering the action, for example to display them in a menu
+    pub fn bindings(&self, action: &Action) -> &[Binding] {
         self.actions.get(action).map_or(&[], Vec::as_slice)
     }
+    /// Short text of the first binding of the action, like `Ctrl+Enter`
+    pub fn shortcut_text(&self, action: &Action) -> Option<String> {
+        self.bindings(action).first().map(Binding::shortcut_text)
+    }
 
     fn just_pressed(
         &self,
@@ -183,19 +234,23 @@ impl EditorControls {
         mouse_input: &ButtonInput<MouseButton>,
         editor: &Editor,
     ) -> bool {
-        let bindings = &self.actions.get(&action).unwrap();
-        bindings
+        self.bindings(&action)
             .iter()
             .any(|binding| binding.just_pressed(keyboard_input, mouse_input, editor))
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn editor_controls_system(
     controls: Res<EditorControls>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     mut editor_events: EventWriter<EditorEvent>,
     mut editor: ResMut<Editor>,
+    mut internal_state: ResMut<EditorInternalState>,
+    mut selection: ResMut<Selection>,
+    mut time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
 ) {
     if controls.just_pressed(
         Action::PlayPauseEditor,
@@ -217,8 +272,10 @@ pub fn editor_controls_system(
         &mouse_input,
         &editor,
     ) {
-        if let Some(default_window) = editor.window_state_mut::<bevy_editor_pls_default_windows::debug_settings::DebugSettingsWindow>() {
-            default_window.pause_time = !default_window.pause_time;
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
         }
     }
 
@@ -231,6 +288,45 @@ pub fn editor_controls_system(
         editor_events.send(EditorEvent::FocusSelected);
     }
 
+    if controls.just_pressed(
+        Action::DeleteSelected,
+        &keyboard_input,
+        &mouse_input,
+        &editor,
+    ) {
+        let selected: Vec<Entity> = selection.iter().collect();
+        #[cfg(feature = "default_windows")]
+        commands.add(move |world: &mut World| {
+            bevy_editor_pls_default_windows::undo::delete_with_undo(world, &selected);
+        });
+        #[cfg(not(feature = "default_windows"))]
+        for entity in selected {
+            if let Some(entity) = commands.get_entity(entity) {
+                entity.despawn_recursive();
+            }
+        }
+        selection.clear();
+    }
+
+    // Ctrl+Shift+Z also contains the Ctrl+Z chord
+    if controls.just_pressed(Action::Redo, &keyboard_input, &mouse_input, &editor) {
+        commands.add(|world: &mut World| {
+            UndoStack::redo(world);
+        });
+    } else if controls.just_pressed(Action::Undo, &keyboard_input, &mouse_input, &editor) {
+        commands.add(|world: &mut World| {
+            UndoStack::undo(world);
+        });
+    }
+
+    for &action in controls.actions.keys() {
+        if let Action::OpenWindow { window, .. } = action {
+            if controls.just_pressed(action, &keyboard_input, &mouse_input, &editor) {
+                editor.open_window_by_id(window, &mut internal_state);
+            }
+        }
+    }
+
     #[cfg(feature = "default_windows")]
     {
         if controls.just_pressed(
@@ -269,15 +365,35 @@ pub fn editor_controls_system(
     }
 }
 
+/// Shows the current bindings next to the menu items triggering the same thing
+pub fn update_shortcut_hints(controls: Res<EditorControls>, mut hints: ResMut<ShortcutHints>) {
+    if !controls.is_changed() {
+        return;
+    }
+
+    hints.toggle_editor = controls.shortcut_text(&Action::PlayPauseEditor);
+    hints.pause_time = controls.shortcut_text(&Action::PauseUnpauseTime);
+    hints.windows = controls
+        .actions
+        .keys()
+        .filter_map(|action| match *action {
+            Action::OpenWindow { window, .. } => Some((window, controls.shortcut_text(action)?)),
+            _ => None,
+        })
+        .collect();
+}
+
 impl EditorControls {
     pub fn unbind(&mut self, action: Action) {
         self.actions.remove(&action);
     }
 
     /// - `C-Enter`: pause time
-    /// - `E`: toggle editor
+    /// - `E` or `F12`: toggle editor
     /// - `F`: focus on selected entity
-    /// `T/R/S`: show translate/rotate/scale gizmo
+    /// - `Delete`: despawn the selected entities
+    /// - `Ctrl+Z`: undo, `Ctrl+Shift+Z` or `Ctrl+Y`: redo
+    /// - `T/R/S`: show translate/rotate/scale gizmo
     pub fn default_bindings() -> Self {
         let mut controls = EditorControls::default();
 
@@ -299,12 +415,74 @@ impl EditorControls {
                 conditions: vec![BindingCondition::ListeningForText(false)],
             },
         );
+        controls.insert(
+            Action::PlayPauseEditor,
+            Binding {
+                input: UserInput::Single(Button::Keyboard(KeyCode::F12)),
+                conditions: vec![BindingCondition::ListeningForText(false)],
+            },
+        );
 
         controls.insert(
             Action::FocusSelected,
             Binding {
                 input: UserInput::Single(Button::Keyboard(KeyCode::KeyF)),
-                conditions: vec![BindingCondition::EditorActive(true)],
+                conditions: vec![
+                    BindingCondition::EditorActive(true),
+                    BindingCondition::ListeningForText(false),
+                ],
+            },
+        );
+
+        controls.insert(
+            Action::DeleteSelected,
+            Binding {
+                input: UserInput::Single(Button::Keyboard(KeyCode::Delete)),
+                conditions: vec![
+                    BindingCondition::EditorActive(true),
+                    BindingCondition::ListeningForText(false),
+                ],
+            },
+        );
+
+        controls.insert(
+            Action::Undo,
+            Binding {
+                input: UserInput::Chord(vec![
+                    Button::Keyboard(KeyCode::ControlLeft),
+                    Button::Keyboard(KeyCode::KeyZ),
+                ]),
+                conditions: vec![
+                    BindingCondition::EditorActive(true),
+                    BindingCondition::ListeningForText(false),
+                ],
+            },
+        );
+        controls.insert(
+            Action::Redo,
+            Binding {
+                input: UserInput::Chord(vec![
+                    Button::Keyboard(KeyCode::ControlLeft),
+                    Button::Keyboard(KeyCode::ShiftLeft),
+                    Button::Keyboard(KeyCode::KeyZ),
+                ]),
+                conditions: vec![
+                    BindingCondition::EditorActive(true),
+                    BindingCondition::ListeningForText(false),
+                ],
+            },
+        );
+        controls.insert(
+            Action::Redo,
+            Binding {
+                input: UserInput::Chord(vec![
+                    Button::Keyboard(KeyCode::ControlLeft),
+                    Button::Keyboard(KeyCode::KeyY),
+                ]),
+                conditions: vec![
+                    BindingCondition::EditorActive(true),
+                    BindingCondition::ListeningForText(false),
+                ],
             },
         );
 
@@ -312,15 +490,24 @@ impl EditorControls {
         {
             controls.insert(
                 Action::SetGizmoModeTranslate,
-                UserInput::Single(Button::Keyboard(KeyCode::KeyT)).into(),
+                Binding {
+                    input: UserInput::Single(Button::Keyboard(KeyCode::KeyT)),
+                    conditions: vec![BindingCondition::ListeningForText(false)],
+                },
             );
             controls.insert(
                 Action::SetGizmoModeRotate,
-                UserInput::Single(Button::Keyboard(KeyCode::KeyR)).into(),
+                Binding {
+                    input: UserInput::Single(Button::Keyboard(KeyCode::KeyR)),
+                    conditions: vec![BindingCondition::ListeningForText(false)],
+                },
             );
             controls.insert(
                 Action::SetGizmoModeScale,
-                UserInput::Single(Button::Keyboard(KeyCode::KeyS)).into(),
+                Binding {
+                    input: UserInput::Single(Button::Keyboard(KeyCode::KeyS)),
+                    conditions: vec![BindingCondition::ListeningForText(false)],
+                },
             );
         }
 
@@ -328,6 +515,51 @@ impl EditorControls {
     }
 }
 
+impl Button {
+    fn shortcut_text(&self) -> String {
+        let Button::Keyboard(key) = self else {
+            return self.to_string();
+        };
+        let name = match key {
+            KeyCode::ControlLeft | KeyCode::ControlRight => "Ctrl",
+            KeyCode::ShiftLeft | KeyCode::ShiftRight => "Shift",
+            KeyCode::AltLeft | KeyCode::AltRight => "Alt",
+            KeyCode::SuperLeft | KeyCode::SuperRight => "Super",
+            KeyCode::Escape => "Esc",
+            KeyCode::Delete => "Del",
+            _ => {
+                let name = format!("{:?}", key);
+                let short = name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name);
+                return short.to_owned();
+            }
+        };
+        name.to_owned()
+    }
+}
+
+impl UserInput {
+    fn shortcut_text(&self) -> String {
+        match self {
+            UserInput::Single(single) => single.shortcut_text(),
+            UserInput::Chord(chord) => chord
+                .iter()
+                .map(Button::shortcut_text)
+                .collect::<Vec<_>>()
+                .join("+"),
+        }
+    }
+}
+
+impl Binding {
+    /// The input without its conditions, formatted like native menu shortcuts (`Ctrl+Enter`)
+    pub fn shortcut_text(&self) -> String {
+        self.input.shortcut_text()
+    }
+}
+
 impl std::fmt::Display for Button {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -389,13 +621,13 @@ impl EditorWindow for ControlsWindow {
     ) {
         let controls = world.get_resource::<EditorControls>().unwrap();
 
-        for action in &[
-            Action::PlayPauseEditor,
-            Action::PauseUnpauseTime,
-            Action::FocusSelected,
-        ] {
+        let window_actions = controls
+            .actions
+            .keys()
+            .filter(|action| matches!(action, Action::OpenWindow { .. }));
+        for action in Action::ALL.iter().chain(window_actions) {
             ui.label(egui::RichText::new(action.to_string()).strong());
-            let bindings = controls.get(action);
+            let bindings = controls.bindings(action);
             for binding in bindings {
                 ui.add(egui::Label::new(format!("{}", binding)).extend());
             }
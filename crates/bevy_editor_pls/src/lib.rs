@@ -1,7 +1,6 @@
 #![allow(clippy::needless_doctest_main)]
 
 /// input settings for the editor UI
-#[cfg(feature = "default_windows")]
 pub mod controls;
 
 use bevy::{
@@ -11,7 +10,10 @@ use bevy::{
 
 pub use bevy_editor_pls_core::egui_dock;
 #[doc(inline)]
-pub use bevy_editor_pls_core::{editor, editor_window, AddEditorWindow};
+pub use bevy_editor_pls_core::{
+    editor, editor_window, viewport_context_menu, AddEditorWindow, EditorViewportContextMenu,
+    Selection,
+};
 pub use egui;
 
 #[cfg(feature = "default_windows")]
@@ -53,6 +55,9 @@ pub enum EditorWindowPlacement {
 #[derive(Default)]
 pub struct EditorPlugin {
     pub window: EditorWindowPlacement,
+    /// Only claim input while the pointer is over editor panels, leaving the game view to the game.
+    /// See [`Editor::passive_input`](editor::Editor::passive_input).
+    pub passive_input: bool,
 }
 
 impl EditorPlugin {
@@ -65,6 +70,12 @@ impl EditorPlugin {
         self.window = EditorWindowPlacement::New(window);
         self
     }
+    /// Run the editor as an overlay which doesn't take over the game's input or cameras.
+    pub fn passive_input(mut self) -> Self {
+        self.passive_input = true;
+        self
+    }
+
     /// Start the editor on the second window ([`MonitorSelection::Index(1)`].
     pub fn on_second_monitor_fullscreen(self) -> Self {
         self.in_new_window(Window {
@@ -92,13 +103,28 @@ impl Plugin for EditorPlugin {
             EditorWindowPlacement::Primary => WindowRef::Primary,
         };
 
-        app.add_plugins(bevy_editor_pls_core::EditorPlugin { window });
+        app.add_plugins(bevy_editor_pls_core::EditorPlugin {
+            window,
+            passive_input: self.passive_input,
+        });
 
         // if !app.is_plugin_added::<bevy_framepace::FramepacePlugin>() {
         //     app.add_plugins(bevy_framepace::FramepacePlugin);
         //     app.add_plugins(bevy_framepace::debug::DiagnosticsPlugin);
         // }
 
+        // keep the controls if the user inserted their own before adding the plugin
+        if !app.world().contains_resource::<controls::EditorControls>() {
+            app.insert_resource(controls::EditorControls::default_bindings());
+        }
+        app.add_systems(
+            Update,
+            (
+                controls::editor_controls_system,
+                controls::update_shortcut_hints,
+            ),
+        );
+
         #[cfg(feature = "default_windows")]
         {
             use bevy_editor_pls_default_windows::add::AddWindow;
@@ -108,10 +134,13 @@ impl Plugin for EditorPlugin {
             use bevy_editor_pls_default_windows::diagnostics::DiagnosticsWindow;
             use bevy_editor_pls_default_windows::gizmos::GizmoWindow;
             use bevy_editor_pls_default_windows::hierarchy::HierarchyWindow;
+            use bevy_editor_pls_default_windows::history::HistoryWindow;
             use bevy_editor_pls_default_windows::inspector::InspectorWindow;
+            use bevy_editor_pls_default_windows::notes::NotesWindow;
             use bevy_editor_pls_default_windows::renderer::RendererWindow;
             use bevy_editor_pls_default_windows::resources::ResourcesWindow;
             use bevy_editor_pls_default_windows::scenes::SceneWindow;
+            use bevy_editor_pls_default_windows::visibility::VisibilityWindow;
 
             app.add_editor_window::<HierarchyWindow>();
             app.add_editor_window::<AssetsWindow>();
@@ -124,19 +153,19 @@ impl Plugin for EditorPlugin {
             app.add_editor_window::<ResourcesWindow>();
             app.add_editor_window::<SceneWindow>();
             app.add_editor_window::<GizmoWindow>();
+            app.add_editor_window::<VisibilityWindow>();
+            app.add_editor_window::<NotesWindow>();
+            app.add_editor_window::<HistoryWindow>();
             app.add_editor_window::<controls::ControlsWindow>();
 
-            app.add_plugins(bevy::pbr::wireframe::WireframePlugin);
-
             // required for the GizmoWindow
             if !app.is_plugin_added::<transform_gizmo_bevy::TransformGizmoPlugin>() {
                 app.add_plugins(transform_gizmo_bevy::TransformGizmoPlugin);
             }
 
-            app.insert_resource(controls::EditorControls::default_bindings())
-                .add_systems(Update, controls::editor_controls_system);
-
-            let mut internal_state = app.world_mut().resource_mut::<editor::EditorInternalState>();
+            let mut internal_state = app
+                .world_mut()
+                .resource_mut::<editor::EditorInternalState>();
 
             let [game, _inspector] =
                 internal_state.split_right::<InspectorWindow>(egui_dock::NodeIndex::root(), 0.75);
@@ -1,18 +1,356 @@
 use std::any::{Any, TypeId};
-
-use bevy::window::WindowMode;
-use bevy::{prelude::*, utils::HashMap};
-use bevy_inspector_egui::bevy_egui::{egui, EguiContext};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::window::{PrimaryWindow, WindowMode, WindowTheme};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_inspector_egui::bevy_egui::{egui, EguiContext, EguiSettings};
 use egui_dock::{NodeIndex, SurfaceIndex, TabBarStyle, TabIndex};
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 
 use crate::editor_window::{EditorWindow, EditorWindowContext};
+use crate::textures::EditorTextures;
+use crate::viewport_context_menu::{EditorViewportContextMenu, ViewportCursor};
 
 #[non_exhaustive]
 #[derive(Event)]
 pub enum EditorEvent {
-    Toggle { now_active: bool },
+    Toggle {
+        now_active: bool,
+    },
     FocusSelected,
+    /// Move the editor camera to see all entities of the scene
+    FrameAll,
+    /// The [`Selection`](crate::Selection) changed, `selected` is the new selection
+    SelectionChanged {
+        selected: Vec<Entity>,
+    },
+    /// An editor window was docked or opened as a floating window, and wasn't open before
+    WindowOpened {
+        type_id: TypeId,
+    },
+    /// An editor window is neither docked nor floating anymore
+    WindowClosed {
+        type_id: TypeId,
+    },
+}
+
+/// Text of the keyboard shortcuts bound to editor actions, shown next to the menu items triggering them.
+///
+/// Kept up to date by whoever handles the input, so the menus don't depend on how shortcuts are bound.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ShortcutHints {
+    /// Shortcut toggling the editor
+    pub toggle_editor: Option<String>,
+    /// Shortcut pausing and resuming the game's virtual time
+    pub pause_time: Option<String>,
+    /// Shortcuts opening an editor window, by the window's `TypeId`
+    pub windows: HashMap<TypeId, String>,
+}
+
+impl ShortcutHints {
+    pub fn window<W: EditorWindow + ?Sized>(&self) -> Option<&str> {
+        self.windows.get(&TypeId::of::<W>()).map(String::as_str)
+    }
+}
+
+/// User-configurable editor settings
+#[derive(Resource)]
+pub struct EditorConfig {
+    /// Paint a solid background behind the docked panels, so the game only shows through the viewport
+    pub opaque_background: bool,
+    pub panel_styles: PanelStyles,
+    /// File the states of windows implementing [`EditorWindow::save_state`] are loaded from on startup and saved to on exit.
+    /// In the [`default_folder`](EditorConfig::default_folder) by default, `None` saves nothing.
+    pub window_state_path: Option<PathBuf>,
+    /// File the dock layout is loaded from on startup and saved to on exit.
+    /// In the [`default_folder`](EditorConfig::default_folder) by default, `None` saves nothing.
+    pub layout_path: Option<PathBuf>,
+    /// File settings changed in the editor UI, like the [theme](EditorConfig::theme) and UI scale, are loaded from on startup and saved to on exit.
+    /// In the [`default_folder`](EditorConfig::default_folder) by default, `None` saves nothing.
+    pub settings_path: Option<PathBuf>,
+    /// Visuals of the egui context, picked in the menu bar. `None` leaves the visuals alone.
+    pub theme: Option<EditorTheme>,
+    /// Scale of the editor UI on top of the window's scale factor, also changed with Ctrl+scroll over the menu bar
+    /// and in the debug settings. Saved to [`EditorConfig::settings_path`] once it was changed.
+    ///
+    /// Applied through [`EguiSettings::scale_factor`], so it affects every egui UI. `None` leaves the setting alone.
+    pub ui_scale: Option<f32>,
+}
+
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 3.0;
+
+impl Default for EditorConfig {
+    fn default() -> Self {
+        let config = EditorConfig {
+            opaque_background: true,
+            panel_styles: PanelStyles::default(),
+            window_state_path: None,
+            layout_path: None,
+            settings_path: None,
+            theme: None,
+            ui_scale: None,
+        };
+        match EditorConfig::default_folder() {
+            Some(folder) => config.persist_in(folder),
+            None => config,
+        }
+    }
+}
+
+impl EditorConfig {
+    /// Keeps the window states, dock layout and settings across sessions, in RON files in `folder`
+    pub fn persist_in(mut self, folder: impl AsRef<Path>) -> Self {
+        let folder = folder.as_ref();
+        self.window_state_path = Some(folder.join("editor_window_state.ron"));
+        self.layout_path = Some(folder.join("editor_layout.ron"));
+        self.settings_path = Some(folder.join("editor_settings.ron"));
+        self
+    }
+
+    /// Saves nothing across sessions
+    pub fn without_persistence(mut self) -> Self {
+        self.window_state_path = None;
+        self.layout_path = None;
+        self.settings_path = None;
+        self
+    }
+
+    /// Folder the editor state is kept in unless configured otherwise: `bevy_editor_pls/<executable name>`
+    /// in the platform's config folder, like `~/.config` on Linux or `%APPDATA%` on Windows.
+    /// `None` where there is no such folder, like on the web.
+    pub fn default_folder() -> Option<PathBuf> {
+        let config_folder = if cfg!(target_os = "windows") {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            std::env::var_os("HOME").map(|home| {
+                PathBuf::from(home)
+                    .join("Library")
+                    .join("Application Support")
+            })
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .filter(|folder| folder.is_absolute())
+                .or_else(|| {
+                    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+                })
+        }?;
+        let executable = std::env::current_exe().ok()?;
+        let app_name = executable.file_stem()?;
+        Some(config_folder.join("bevy_editor_pls").join(app_name))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditorTheme {
+    Dark,
+    Light,
+    /// Dark or light like the theme the window system reports for the editor's window, dark if it reports none
+    FollowSystem,
+}
+
+impl EditorTheme {
+    pub const ALL: &'static [EditorTheme] = &[
+        EditorTheme::Dark,
+        EditorTheme::Light,
+        EditorTheme::FollowSystem,
+    ];
+}
+
+impl std::fmt::Display for EditorTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditorTheme::Dark => write!(f, "Dark"),
+            EditorTheme::Light => write!(f, "Light"),
+            EditorTheme::FollowSystem => write!(f, "Follow system"),
+        }
+    }
+}
+
+/// Visuals for the separate regions of the editor. Regions set to `None` use the style of the egui context.
+#[derive(Clone, Default)]
+pub struct PanelStyles {
+    pub menu_bar: Option<egui::Visuals>,
+    pub viewport_toolbar: Option<egui::Visuals>,
+    pub docked_panels: Option<egui::Visuals>,
+    /// Background of the tab bars, defaults to the window fill of the docked panels
+    pub tab_bar_fill: Option<egui::Color32>,
+}
+
+/// Customizes the style of the editor's menu bar and docked panels, e.g. to match a game's branding.
+///
+/// Called every frame on the style of the egui context, after the [`PanelStyles`] were applied.
+/// The style of the context itself isn't changed, so the game's own egui UI keeps its look.
+#[derive(Resource)]
+pub struct EditorStyle {
+    pub apply: Box<dyn Fn(&mut egui::Style) + Send + Sync>,
+}
+
+impl EditorStyle {
+    pub fn new(apply: impl Fn(&mut egui::Style) + Send + Sync + 'static) -> Self {
+        EditorStyle {
+            apply: Box::new(apply),
+        }
+    }
+}
+
+/// The style of the context, with the visuals replaced if there are any and the [`EditorStyle`] applied
+fn style_with_visuals(
+    ctx: &egui::Context,
+    visuals: Option<&egui::Visuals>,
+    editor_style: Option<&EditorStyle>,
+) -> egui::Style {
+    let mut style = (*ctx.style()).clone();
+    if let Some(visuals) = visuals {
+        style.visuals = visuals.clone();
+    }
+    if let Some(editor_style) = editor_style {
+        (editor_style.apply)(&mut style);
+    }
+    style
+}
+
+/// Which pointer buttons are held, and whether each press started in the viewport instead of on editor UI.
+///
+/// Lets editor tools like camera controllers react to drags without reading egui input themselves.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EditorPointerState {
+    pub press_active: bool,
+    pub press_start_in_viewport: bool,
+    pub secondary_press_active: bool,
+    pub secondary_press_start_in_viewport: bool,
+    pub middle_press_active: bool,
+    pub middle_press_start_in_viewport: bool,
+    /// Scrolled amount this frame while the pointer is over the viewport, zero while scrolling editor UI
+    pub viewport_scroll_delta: egui::Vec2,
+    /// Position of the pointer in egui points while it is over the viewport and not covered by a menu
+    pub viewport_pointer_pos: Option<egui::Pos2>,
+    /// How far the pointer moved since the last frame while a button is held whose press started in the viewport.
+    /// `None` when not dragging, or when the drag started on editor UI.
+    pub viewport_drag_delta: Option<egui::Vec2>,
+    last_pointer_pos: Option<egui::Pos2>,
+    /// Held modifier keys, all released while text input is active
+    pub modifiers: EditorModifiers,
+}
+
+/// Snapshot of the modifier keys held this frame
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EditorModifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    /// `Cmd` on macOS, `Ctrl` everywhere else
+    pub command: bool,
+}
+
+impl From<egui::Modifiers> for EditorModifiers {
+    fn from(modifiers: egui::Modifiers) -> Self {
+        EditorModifiers {
+            ctrl: modifiers.ctrl,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+            command: modifiers.command,
+        }
+    }
+}
+
+impl EditorPointerState {
+    fn update(&mut self, ctx: &egui::Context, in_viewport: bool, listening_for_text: bool) {
+        let down = |button| ctx.input(|input| input.pointer.button_down(button));
+        track_press(
+            &mut self.press_active,
+            &mut self.press_start_in_viewport,
+            down(egui::PointerButton::Primary),
+            in_viewport,
+        );
+        track_press(
+            &mut self.secondary_press_active,
+            &mut self.secondary_press_start_in_viewport,
+            down(egui::PointerButton::Secondary),
+            in_viewport,
+        );
+        track_press(
+            &mut self.middle_press_active,
+            &mut self.middle_press_start_in_viewport,
+            down(egui::PointerButton::Middle),
+            in_viewport,
+        );
+
+        let (scroll_delta, has_pointer) =
+            ctx.input(|input| (input.raw_scroll_delta, input.pointer.has_pointer()));
+        self.viewport_scroll_delta = match in_viewport && has_pointer {
+            true => scroll_delta,
+            false => egui::Vec2::ZERO,
+        };
+
+        let dragging = (self.press_active && self.press_start_in_viewport)
+            || (self.secondary_press_active && self.secondary_press_start_in_viewport)
+            || (self.middle_press_active && self.middle_press_start_in_viewport);
+        let pointer_pos = ctx.input(|input| input.pointer.latest_pos());
+        self.viewport_drag_delta = match (dragging, self.last_pointer_pos, pointer_pos) {
+            (true, Some(last), Some(pos)) => Some(pos - last),
+            _ => None,
+        };
+        self.last_pointer_pos = pointer_pos;
+
+        self.modifiers = match listening_for_text {
+            true => EditorModifiers::default(),
+            false => ctx.input(|input| input.modifiers).into(),
+        };
+    }
+}
+
+/// Remembers where a press started for as long as the button is held
+fn track_press(active: &mut bool, start_in_viewport: &mut bool, down: bool, in_viewport: bool) {
+    if down && !*active {
+        *start_in_viewport = in_viewport;
+    } else if !down {
+        *start_in_viewport = false;
+    }
+    *active = down;
+}
+
+/// Maps a position in egui points to logical pixels relative to a camera viewport starting at `viewport_min`,
+/// as expected by [`Camera::viewport_to_world`].
+///
+/// `pixels_per_point` is the one of the egui context (the window scale factor times [`EguiSettings::scale_factor`]),
+/// `window_scale_factor` the [`Window::scale_factor`] the camera converts its physical viewport with.
+/// The camera viewport starts at the physical pixel `viewport_min` is rounded down to, so that is the origin.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_editor_pls_core::editor::viewport_local_position;
+/// # use bevy_inspector_egui::egui;
+/// // a 1.5x window scale factor with an editor UI scale of 1
+/// let pointer = viewport_local_position(egui::pos2(200., 150.), egui::pos2(100., 50.), 1.5, 1.5);
+/// assert_eq!(pointer, Vec2::new(100., 100.));
+///
+/// // the viewport starts at physical pixel (151, 76), a third of a logical pixel before its egui corner
+/// let corner = viewport_local_position(egui::pos2(101., 51.), egui::pos2(101., 51.), 1.5, 1.5);
+/// assert!(corner.abs_diff_eq(Vec2::splat(1. / 3.), 1e-5));
+/// ```
+pub fn viewport_local_position(
+    pos: egui::Pos2,
+    viewport_min: egui::Pos2,
+    pixels_per_point: f32,
+    window_scale_factor: f32,
+) -> Vec2 {
+    let physical = Vec2::new(pos.x, pos.y) * pixels_per_point;
+    (physical - viewport_physical_min(viewport_min, pixels_per_point)) / window_scale_factor
+}
+
+/// Physical position of the camera viewport, truncated like `set_main_pass_viewport` in the editor cameras
+fn viewport_physical_min(viewport_min: egui::Pos2, pixels_per_point: f32) -> Vec2 {
+    (Vec2::new(viewport_min.x, viewport_min.y) * pixels_per_point)
+        .max(Vec2::ZERO)
+        .floor()
 }
 
 #[derive(Debug)]
@@ -25,13 +363,23 @@ enum ActiveEditorInteraction {
 pub struct Editor {
     on_window: Entity,
     always_active: bool,
+    passive_input: bool,
 
     active: bool,
 
     pointer_used: bool,
     active_editor_interaction: Option<ActiveEditorInteraction>,
+    pointer_state: EditorPointerState,
     listening_for_text: bool,
     viewport: egui::Rect,
+    /// `egui::Context::pixels_per_point` the editor UI was last drawn with
+    pixels_per_point: f32,
+    /// [`Window::scale_factor`] of the editor window when the UI was last drawn
+    window_scale_factor: f32,
+    /// Where the viewport context menu was opened
+    context_menu_pos: Option<egui::Pos2>,
+    /// Index of the [`EditorViewportContextMenu`] entry to run after the UI
+    context_menu_action: Option<usize>,
 
     windows: IndexMap<TypeId, EditorWindowData>,
     window_states: HashMap<TypeId, EditorWindowState>,
@@ -41,18 +389,25 @@ impl Editor {
         Editor {
             on_window,
             always_active,
+            passive_input: false,
 
             active: always_active,
             pointer_used: false,
             active_editor_interaction: None,
+            pointer_state: EditorPointerState::default(),
             listening_for_text: false,
             viewport: egui::Rect::from_min_size(egui::Pos2::ZERO, egui::Vec2::new(640., 480.)),
+            pixels_per_point: 1.0,
+            window_scale_factor: 1.0,
+            context_menu_pos: None,
+            context_menu_action: None,
 
             windows: IndexMap::default(),
             window_states: HashMap::default(),
         }
     }
 
+    /// The window the editor is shown on. If that window is closed, the editor moves to the primary window.
     pub fn window(&self) -> Entity {
         self.on_window
     }
@@ -63,6 +418,15 @@ impl Editor {
         self.active
     }
 
+    /// In passive mode the editor only claims input while the pointer is over its own panels.
+    /// Being active doesn't take over the game view, so gameplay input and cameras keep working in the viewport.
+    pub fn passive_input(&self) -> bool {
+        self.passive_input
+    }
+    pub fn set_passive_input(&mut self, passive_input: bool) {
+        self.passive_input = passive_input;
+    }
+
     /// Panics if `self.always_active` is true
     pub fn set_active(&mut self, active: bool) {
         if !active && self.always_active {
@@ -78,7 +442,52 @@ impl Editor {
     pub fn is_in_viewport(&self, pos: egui::Pos2) -> bool {
         self.viewport.contains(pos)
     }
+    /// Physical pixels per egui point the editor UI was last drawn with,
+    /// combining [`Window::scale_factor`] and [`EguiSettings::scale_factor`].
+    pub fn pixels_per_point(&self) -> f32 {
+        self.pixels_per_point
+    }
 
+    /// Maps a position in egui points to logical pixels relative to the cameras rendering to the viewport,
+    /// as expected by [`Camera::viewport_to_world`]. See [`viewport_local_position`].
+    pub fn viewport_position(&self, pos: egui::Pos2) -> Vec2 {
+        viewport_local_position(
+            pos,
+            self.viewport.min,
+            self.pixels_per_point,
+            self.window_scale_factor,
+        )
+    }
+    /// Inverse of [`Editor::viewport_position`], e.g. for positions from [`Camera::world_to_viewport`]
+    pub fn viewport_position_to_egui(&self, viewport_position: Vec2) -> egui::Pos2 {
+        let physical_min = viewport_physical_min(self.viewport.min, self.pixels_per_point);
+        let physical = viewport_position * self.window_scale_factor + physical_min;
+        egui::pos2(physical.x, physical.y) / self.pixels_per_point
+    }
+
+    /// Ray through the pointer from a camera rendering to the viewport, like the editor camera.
+    /// `None` if the pointer isn't over the viewport.
+    pub fn viewport_cursor_ray(
+        &self,
+        camera: &Camera,
+        cam_transform: &GlobalTransform,
+    ) -> Option<Ray3d> {
+        let pos = self.pointer_state.viewport_pointer_pos?;
+        self.viewport_ray(pos, camera, cam_transform)
+    }
+
+    /// Ray through a position in egui coordinates, like the ones of [`Editor::viewport`],
+    /// from a camera rendering to the viewport.
+    pub fn viewport_ray(
+        &self,
+        pos: egui::Pos2,
+        camera: &Camera,
+        cam_transform: &GlobalTransform,
+    ) -> Option<Ray3d> {
+        camera.viewport_to_world(cam_transform, self.viewport_position(pos))
+    }
+
+    /// Whether the pointer is used by the editor UI, either by being over a panel or by an interaction started on one.
     pub fn pointer_used(&self) -> bool {
         self.pointer_used
             || matches!(
@@ -91,7 +500,20 @@ impl Editor {
         self.listening_for_text
     }
 
+    /// The pointer buttons held this frame. Presses are never reported as starting in the viewport
+    /// while the editor is inactive, or in [passive mode](Editor::passive_input).
+    pub fn pointer_state(&self) -> &EditorPointerState {
+        &self.pointer_state
+    }
+
+    /// Whether editor tools (like the editor cameras) may react to pointer input in the viewport.
+    ///
+    /// Always `false` in [passive mode](Editor::passive_input).
     pub fn viewport_interaction_active(&self) -> bool {
+        if self.passive_input {
+            return false;
+        }
+
         !self.pointer_used
             || matches!(
                 self.active_editor_interaction,
@@ -106,10 +528,18 @@ pub(crate) type EditorWindowState = Box<dyn Any + Send + Sync>;
 
 struct EditorWindowData {
     name: &'static str,
+    category: &'static str,
+    icon: Option<&'static str>,
+    viewport_toolbar_order: i32,
     ui_fn: UiFn,
     menu_ui_fn: UiFn,
     viewport_toolbar_ui_fn: UiFn,
     viewport_ui_fn: UiFn,
+    save_state_fn: fn(&EditorWindowState) -> Option<String>,
+    title_fn: fn(&EditorWindowState) -> Option<String>,
+    load_state_fn: fn(&mut EditorWindowState, &str),
+    on_open_fn: fn(&mut World, &mut EditorWindowState),
+    on_close_fn: fn(&mut World, &mut EditorWindowState),
     default_size: (f32, f32),
 }
 
@@ -117,6 +547,15 @@ struct EditorWindowData {
 pub struct EditorInternalState {
     state: egui_dock::DockState<TreeTab>,
     pub(crate) floating_windows: Vec<FloatingWindow>,
+    pub(crate) textures: EditorTextures,
+    /// Docked windows which can't be closed and are kept by "Close others"
+    pinned: HashSet<TypeId>,
+    /// Set from the tab context menu, applied once the dock area is done showing
+    close_others_than: Option<TypeId>,
+    /// [`EditorWindow::NAME`] of every registered window, used to save layouts independent of `TypeId`s
+    pub(crate) window_names: HashMap<TypeId, &'static str>,
+    /// Windows which were open when [`EditorEvent::WindowOpened`]/[`EditorEvent::WindowClosed`] were last sent
+    open_windows: HashSet<TypeId>,
 
     next_floating_window_id: u32,
 }
@@ -126,12 +565,20 @@ impl Default for EditorInternalState {
         Self {
             state: egui_dock::DockState::new(vec![TreeTab::GameView]),
             floating_windows: Default::default(),
+            textures: Default::default(),
+            pinned: Default::default(),
+            close_others_than: None,
+            window_names: Default::default(),
+            open_windows: Default::default(),
             next_floating_window_id: Default::default(),
         }
     }
 }
 
-#[derive(Copy, Clone)]
+/// Name of the viewport tab in saved layouts
+const GAME_VIEW_TAB_NAME: &str = "Viewport";
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 enum TreeTab {
     GameView,
     CustomWindow(TypeId),
@@ -139,8 +586,11 @@ enum TreeTab {
 
 impl EditorInternalState {
     pub fn push_to_focused_leaf<W: EditorWindow>(&mut self) {
+        self.push_to_focused_leaf_by_id(TypeId::of::<W>());
+    }
+    fn push_to_focused_leaf_by_id(&mut self, window: TypeId) {
         self.state
-            .push_to_focused_leaf(TreeTab::CustomWindow(TypeId::of::<W>()));
+            .push_to_focused_leaf(TreeTab::CustomWindow(window));
         if let Some((surface_index, node_index)) = self.state.focused_leaf() {
             self.state
                 .set_active_tab((surface_index, node_index, TabIndex(0)));
@@ -199,6 +649,97 @@ impl EditorInternalState {
         self.state
             .split((SurfaceIndex::main(), parent), split, fraction, node)
     }
+
+    /// Saves the dock layout to a RON file. Windows are stored by their [`EditorWindow::NAME`], so the file stays valid across recompiles.
+    ///
+    /// Tabs of windows added through [`Editor::add_window`] instead of
+    /// [`AddEditorWindow::add_editor_window`](crate::AddEditorWindow::add_editor_window) have no known name and are left out.
+    pub fn save_layout(&self, path: &Path) -> io::Result<()> {
+        let layout = self.state.filter_map_tabs(|tab| match *tab {
+            TreeTab::GameView => Some(GAME_VIEW_TAB_NAME.to_owned()),
+            TreeTab::CustomWindow(window) => {
+                self.window_names.get(&window).map(|&name| name.to_owned())
+            }
+        });
+        let ron = ron::ser::to_string_pretty(&layout, ron::ser::PrettyConfig::default())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        std::fs::write(path, ron)
+    }
+
+    /// Loads a layout saved with [`EditorInternalState::save_layout`].
+    ///
+    /// The current layout is kept if the file references a window that isn't registered.
+    /// The viewport is added if the saved layout doesn't contain it.
+    pub fn load_layout(&mut self, path: &Path) -> io::Result<()> {
+        let contents = std::fs::read_to_string(path)?;
+        let layout: egui_dock::DockState<String> = ron::from_str(&contents)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        let windows: HashMap<&str, TypeId> = self
+            .window_names
+            .iter()
+            .map(|(&window, &name)| (name, window))
+            .collect();
+        let unknown = layout
+            .iter_all_tabs()
+            .map(|(_, name)| name.as_str())
+            .find(|&name| name != GAME_VIEW_TAB_NAME && !windows.contains_key(name));
+        if let Some(unknown) = unknown {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("layout contains unknown window `{unknown}`"),
+            ));
+        }
+
+        let mut state = layout.map_tabs(|name| match windows.get(name.as_str()) {
+            Some(&window) => TreeTab::CustomWindow(window),
+            None => TreeTab::GameView,
+        });
+        if state.find_tab(&TreeTab::GameView).is_none() {
+            state.push_to_first_leaf(TreeTab::GameView);
+        }
+        self.state = state;
+
+        Ok(())
+    }
+
+    pub fn is_pinned<W: EditorWindow>(&self) -> bool {
+        self.pinned.contains(&TypeId::of::<W>())
+    }
+    /// Pinned tabs can't be closed
+    pub fn set_pinned<W: EditorWindow>(&mut self, pinned: bool) {
+        self.set_pinned_by_id(TypeId::of::<W>(), pinned);
+    }
+    fn set_pinned_by_id(&mut self, window: TypeId, pinned: bool) {
+        if pinned {
+            self.pinned.insert(window);
+        } else {
+            self.pinned.remove(&window);
+        }
+    }
+
+    /// Number of editor windows docked as tabs, not counting the viewport
+    pub fn docked_window_count(&self) -> usize {
+        self.state
+            .iter_all_tabs()
+            .filter(|(_, tab)| matches!(tab, TreeTab::CustomWindow(_)))
+            .count()
+    }
+    pub fn has_floating_window<W: EditorWindow>(&self) -> bool {
+        self.has_floating_window_by_id(TypeId::of::<W>())
+    }
+    fn has_floating_window_by_id(&self, window: TypeId) -> bool {
+        self.floating_windows
+            .iter()
+            .any(|floating_window| floating_window.window == window)
+    }
+    pub fn floating_window_count(&self) -> usize {
+        self.floating_windows.len()
+    }
+    /// How many floating window ids were handed out so far
+    pub fn floating_window_ids_used(&self) -> u32 {
+        self.next_floating_window_id
+    }
 }
 
 #[derive(Clone)]
@@ -214,6 +755,41 @@ impl EditorInternalState {
         self.next_floating_window_id += 1;
         id
     }
+
+    /// Sends the window events for windows which were opened or closed since the last call,
+    /// and returns them for their [`EditorWindow::on_open`]/[`EditorWindow::on_close`] hooks
+    fn send_window_events(&mut self, editor_events: &mut Events<EditorEvent>) -> WindowChanges {
+        let docked = self
+            .state
+            .iter_all_tabs()
+            .filter_map(|(_, tab)| match *tab {
+                TreeTab::CustomWindow(window) => Some(window),
+                TreeTab::GameView => None,
+            });
+        let floating = self
+            .floating_windows
+            .iter()
+            .map(|floating_window| floating_window.window);
+        let open: HashSet<TypeId> = docked.chain(floating).collect();
+
+        let opened: Vec<TypeId> = open.difference(&self.open_windows).copied().collect();
+        let closed: Vec<TypeId> = self.open_windows.difference(&open).copied().collect();
+        for &type_id in &opened {
+            editor_events.send(EditorEvent::WindowOpened { type_id });
+        }
+        for &type_id in &closed {
+            editor_events.send(EditorEvent::WindowClosed { type_id });
+        }
+        self.open_windows = open;
+
+        WindowChanges { opened, closed }
+    }
+}
+
+/// Windows opened and closed during the last frame
+struct WindowChanges {
+    opened: Vec<TypeId>,
+    closed: Vec<TypeId>,
 }
 
 fn ui_fn<W: EditorWindow>(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
@@ -232,6 +808,27 @@ fn viewport_toolbar_ui_fn<W: EditorWindow>(
 fn viewport_ui_fn<W: EditorWindow>(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
     W::viewport_ui(world, cx, ui);
 }
+fn save_state_fn<W: EditorWindow>(state: &EditorWindowState) -> Option<String> {
+    W::save_state(state.downcast_ref::<W::State>()?)
+}
+fn title_fn<W: EditorWindow>(state: &EditorWindowState) -> Option<String> {
+    W::title(state.downcast_ref::<W::State>()?)
+}
+fn load_state_fn<W: EditorWindow>(state: &mut EditorWindowState, saved: &str) {
+    if let Some(state) = state.downcast_mut::<W::State>() {
+        W::load_state(state, saved);
+    }
+}
+fn on_open_fn<W: EditorWindow>(world: &mut World, state: &mut EditorWindowState) {
+    if let Some(state) = state.downcast_mut::<W::State>() {
+        W::on_open(world, state);
+    }
+}
+fn on_close_fn<W: EditorWindow>(world: &mut World, state: &mut EditorWindowState) {
+    if let Some(state) = state.downcast_mut::<W::State>() {
+        W::on_close(world, state);
+    }
+}
 
 impl Editor {
     pub fn add_window<W: EditorWindow>(&mut self) {
@@ -245,7 +842,15 @@ impl Editor {
             menu_ui_fn,
             viewport_toolbar_ui_fn,
             viewport_ui_fn,
+            save_state_fn: save_state_fn::<W>,
+            load_state_fn: load_state_fn::<W>,
+            title_fn: title_fn::<W>,
+            on_open_fn: on_open_fn::<W>,
+            on_close_fn: on_close_fn::<W>,
             name: W::NAME,
+            category: W::CATEGORY,
+            icon: W::ICON,
+            viewport_toolbar_order: W::viewport_toolbar_order(),
             default_size: W::DEFAULT_SIZE,
         };
         if self.windows.insert(type_id, data).is_some() {
@@ -268,11 +873,125 @@ impl Editor {
             .get(&TypeId::of::<W>())
             .and_then(|s| s.downcast_ref::<W::State>())
     }
+
+    /// Focuses the tab of the window, docking it into the focused leaf if it isn't open yet.
+    /// Returns `false` if the window was never added with [`Editor::add_window`].
+    pub fn open_window<W: EditorWindow>(
+        &mut self,
+        internal_state: &mut EditorInternalState,
+    ) -> bool {
+        self.open_window_by_id(TypeId::of::<W>(), internal_state)
+    }
+
+    /// Like [`Editor::open_window`], for windows only known by their `TypeId`
+    pub fn open_window_by_id(
+        &mut self,
+        window: TypeId,
+        internal_state: &mut EditorInternalState,
+    ) -> bool {
+        if !self.windows.contains_key(&window) {
+            return false;
+        }
+
+        match internal_state
+            .state
+            .find_tab(&TreeTab::CustomWindow(window))
+        {
+            Some((surface, node, tab)) => {
+                internal_state.state.set_active_tab((surface, node, tab));
+                internal_state
+                    .state
+                    .set_focused_node_and_surface((surface, node));
+            }
+            None => internal_state.push_to_focused_leaf_by_id(window),
+        }
+        true
+    }
+
+    /// Whether the window is docked as a tab or shown as a floating window
+    pub fn is_window_open<W: EditorWindow>(&self, internal_state: &EditorInternalState) -> bool {
+        let window = TypeId::of::<W>();
+        internal_state
+            .state
+            .find_tab(&TreeTab::CustomWindow(window))
+            .is_some()
+            || internal_state.has_floating_window_by_id(window)
+    }
+
+    /// Removes the tab of the window and closes its floating windows.
+    /// Returns `false` if the window wasn't open.
+    pub fn close_window<W: EditorWindow>(
+        &mut self,
+        internal_state: &mut EditorInternalState,
+    ) -> bool {
+        let window = TypeId::of::<W>();
+
+        let mut closed = false;
+        while let Some(tab) = internal_state
+            .state
+            .find_tab(&TreeTab::CustomWindow(window))
+        {
+            internal_state.state.remove_tab(tab);
+            closed = true;
+        }
+
+        let floating_windows = internal_state.floating_windows.len();
+        internal_state
+            .floating_windows
+            .retain(|floating_window| floating_window.window != window);
+        closed |= internal_state.floating_windows.len() != floating_windows;
+
+        closed
+    }
+
+    /// [`EditorWindow::title`] of the window, falling back to its name, after its icon
+    fn window_title(&self, window: TypeId) -> String {
+        let data = &self.windows[&window];
+        let title = self
+            .window_states
+            .get(&window)
+            .and_then(data.title_fn)
+            .unwrap_or_else(|| data.name.to_owned());
+        match data.icon {
+            Some(icon) => format!("{icon} {title}"),
+            None => title,
+        }
+    }
+
+    /// The state of every window implementing [`EditorWindow::save_state`], by window name
+    pub fn save_window_states(&self) -> BTreeMap<String, String> {
+        self.windows
+            .iter()
+            .filter_map(|(type_id, window)| {
+                let state = self.window_states.get(type_id)?;
+                let saved = (window.save_state_fn)(state)?;
+                Some((window.name.to_owned(), saved))
+            })
+            .collect()
+    }
+
+    /// Restores window states returned from [`Editor::save_window_states`]. Unknown window names are ignored.
+    pub fn load_window_states(&mut self, saved: &BTreeMap<String, String>) {
+        for (type_id, window) in self.windows.iter() {
+            let (Some(saved), Some(state)) =
+                (saved.get(window.name), self.window_states.get_mut(type_id))
+            else {
+                continue;
+            };
+            (window.load_state_fn)(state, saved);
+        }
+    }
 }
 
 impl Editor {
     pub(crate) fn system(world: &mut World) {
+        apply_ui_scale(world);
+
         world.resource_scope(|world, mut editor: Mut<Editor>| {
+            if world.get_entity(editor.on_window).is_none() {
+                editor.on_window_despawned(world);
+            }
+
             let Ok(mut egui_context) = world
                 .query::<&mut EguiContext>()
                 .get_mut(world, editor.on_window)
@@ -280,6 +999,7 @@ impl Editor {
                 return;
             };
             let egui_context = egui_context.get_mut().clone();
+            apply_theme(world, &egui_context, editor.on_window);
 
             world.resource_scope(
                 |world, mut editor_internal_state: Mut<EditorInternalState>| {
@@ -290,10 +1010,84 @@ impl Editor {
                             &mut editor_internal_state,
                             &mut editor_events,
                         );
+                        let changes = editor_internal_state.send_window_events(&mut editor_events);
+                        editor.run_window_hooks(world, &changes);
                     });
+                    editor_internal_state.textures.release_unused(world);
                 },
             );
         });
+
+        Editor::run_viewport_context_menu_action(world);
+    }
+
+    /// Calls [`EditorWindow::on_close`] and then [`EditorWindow::on_open`] of the changed windows
+    fn run_window_hooks(&mut self, world: &mut World, changes: &WindowChanges) {
+        for type_id in &changes.closed {
+            let (Some(window), Some(state)) = (
+                self.windows.get(type_id),
+                self.window_states.get_mut(type_id),
+            ) else {
+                continue;
+            };
+            (window.on_close_fn)(world, state);
+        }
+        for type_id in &changes.opened {
+            let (Some(window), Some(state)) = (
+                self.windows.get(type_id),
+                self.window_states.get_mut(type_id),
+            ) else {
+                continue;
+            };
+            (window.on_open_fn)(world, state);
+        }
+    }
+
+    /// Moves the editor to the primary window when the window it was shown on got closed,
+    /// or deactivates it if there is no primary window either.
+    fn on_window_despawned(&mut self, world: &mut World) {
+        let primary_window = world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .get_single(world)
+            .ok();
+
+        let was_active = self.active;
+        match primary_window {
+            Some(primary_window) => {
+                warn!(
+                    "editor window {:?} no longer exists, moving the editor to the primary window",
+                    self.on_window
+                );
+                self.on_window = primary_window;
+            }
+            None if was_active => warn!(
+                "editor window {:?} no longer exists and there is no primary window, deactivating the editor",
+                self.on_window
+            ),
+            None => return,
+        }
+
+        // the editor now shares a window with the game (or has none), so it has to be possible to hide it
+        self.always_active = false;
+        self.active = false;
+        if was_active {
+            world.send_event(EditorEvent::Toggle { now_active: false });
+        }
+    }
+
+    /// Runs the entry picked in the viewport context menu, now that the editor resources are available again
+    fn run_viewport_context_menu_action(world: &mut World) {
+        let mut editor = world.resource_mut::<Editor>();
+        let (Some(index), Some(pos)) = (editor.context_menu_action.take(), editor.context_menu_pos)
+        else {
+            return;
+        };
+        let (window, viewport_position) = (editor.on_window, editor.viewport_position(pos));
+
+        let cursor = ViewportCursor::new(world, window, viewport_position);
+        world.resource_scope(|world, context_menu: Mut<EditorViewportContextMenu>| {
+            context_menu.run(index, world, cursor);
+        });
     }
 
     fn editor_ui(
@@ -303,26 +1097,45 @@ impl Editor {
         internal_state: &mut EditorInternalState,
         editor_events: &mut Events<EditorEvent>,
     ) {
-        self.editor_menu_bar(world, ctx, internal_state, editor_events);
+        let panel_styles = world.resource::<EditorConfig>().panel_styles.clone();
+
+        self.editor_menu_bar(world, ctx, &panel_styles, internal_state, editor_events);
 
         if !self.active {
             self.editor_floating_windows(world, ctx, internal_state);
             self.pointer_used = ctx.wants_pointer_input();
+            self.pointer_state.viewport_pointer_pos = None;
+            self.pointer_state
+                .update(ctx, false, ctx.wants_keyboard_input());
             return;
         }
 
+        if world.resource::<EditorConfig>().opaque_background {
+            let editor_style = world.get_resource::<EditorStyle>();
+            let fill = style_with_visuals(ctx, panel_styles.docked_panels.as_ref(), editor_style)
+                .visuals
+                .panel_fill;
+            paint_background_outside_viewport(ctx, self.viewport, fill);
+        }
+
         let mut tree = std::mem::replace(
             &mut internal_state.state,
             egui_dock::DockState::new(Vec::new()),
         );
 
+        let editor_style = world.get_resource::<EditorStyle>();
+        let has_editor_style = editor_style.is_some();
+        let panel_style =
+            style_with_visuals(ctx, panel_styles.docked_panels.as_ref(), editor_style);
         egui_dock::DockArea::new(&mut tree)
             .style(egui_dock::Style {
                 tab_bar: TabBarStyle {
-                    bg_fill: ctx.style().visuals.window_fill(),
+                    bg_fill: panel_styles
+                        .tab_bar_fill
+                        .unwrap_or(panel_style.visuals.window_fill()),
                     ..default()
                 },
-                ..egui_dock::Style::from_egui(ctx.style().as_ref())
+                ..egui_dock::Style::from_egui(&panel_style)
             })
             .show(
                 ctx,
@@ -330,12 +1143,31 @@ impl Editor {
                     editor: self,
                     internal_state,
                     world,
+                    panel_styles: &panel_styles,
+                    docked_style: has_editor_style.then_some(&panel_style),
                 },
             );
+        if let Some(keep) = internal_state.close_others_than.take() {
+            let pinned = &internal_state.pinned;
+            tree.retain_tabs(|tab| match *tab {
+                TreeTab::GameView => true,
+                TreeTab::CustomWindow(window) => window == keep || pinned.contains(&window),
+            });
+        }
         internal_state.state = tree;
 
         let pointer_pos = ctx.input(|input| input.pointer.interact_pos());
-        self.pointer_used = pointer_pos.map_or(false, |pos| !self.is_in_viewport(pos));
+        // menus opened over the viewport (like its context menu) shouldn't pass clicks through to the viewport
+        let over_menu = pointer_pos
+            .and_then(|pos| ctx.layer_id_at(pos))
+            .is_some_and(|layer| layer.order == egui::Order::Foreground);
+        self.pointer_used = over_menu || pointer_pos.is_some_and(|pos| !self.is_in_viewport(pos));
+        self.pointer_state.viewport_pointer_pos = pointer_pos.filter(|_| !self.pointer_used);
+        // the scale the UI was laid out with, `EguiSettings` may already have changed
+        self.pixels_per_point = ctx.pixels_per_point();
+        self.window_scale_factor = world
+            .get::<Window>(self.on_window)
+            .map_or(1.0, Window::scale_factor);
 
         self.editor_floating_windows(world, ctx, internal_state);
 
@@ -352,49 +1184,125 @@ impl Editor {
             }
             (Some(_), true) => {}
         }
+
+        let in_viewport = !self.pointer_used && !self.passive_input;
+        self.pointer_state
+            .update(ctx, in_viewport, self.listening_for_text);
     }
 
     fn editor_menu_bar(
         &mut self,
         world: &mut World,
         ctx: &egui::Context,
+        panel_styles: &PanelStyles,
         internal_state: &mut EditorInternalState,
         editor_events: &mut Events<EditorEvent>,
     ) {
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            let bar_response = egui::menu::bar(ui, |ui| {
-                if !self.always_active && play_pause_button(self.active, ui).clicked() {
-                    self.active = !self.active;
-                    editor_events.send(EditorEvent::Toggle {
-                        now_active: self.active,
+        let editor_style = world.get_resource::<EditorStyle>();
+        let style = style_with_visuals(ctx, panel_styles.menu_bar.as_ref(), editor_style);
+        egui::TopBottomPanel::top("top_panel")
+            .frame(egui::Frame::side_top_panel(&style))
+            .show(ctx, |ui| {
+                ui.set_style(style.clone());
+
+                let hints = world.get_resource::<ShortcutHints>();
+                let toggle_hint = hints.and_then(|hints| hints.toggle_editor.clone());
+                let pause_hint = hints.and_then(|hints| hints.pause_time.clone());
+                let (toggle_hint, pause_hint) = (toggle_hint.as_deref(), pause_hint.as_deref());
+
+                let bar_response = egui::menu::bar(ui, |ui| {
+                    if self.always_active {
+                        // nothing to toggle, but still show that the editor is active
+                        ui.add_enabled(false, egui::Button::new("▶").frame(false))
+                            .on_disabled_hover_text("The editor is always active");
+                    } else if play_pause_button(self.active, toggle_hint, ui).clicked() {
+                        self.active = !self.active;
+                        editor_events.send(EditorEvent::Toggle {
+                            now_active: self.active,
+                        });
+                    }
+
+                    let mut step_frame = false;
+                    if let Some(mut time) = world.get_resource_mut::<Time<Virtual>>() {
+                        if game_pause_button(time.is_paused(), pause_hint, ui).clicked() {
+                            if time.is_paused() {
+                                time.unpause();
+                            } else {
+                                time.pause();
+                            }
+                        }
+                        if frame_step_button(time.is_paused(), self.active, ui).clicked() {
+                            time.unpause();
+                            step_frame = true;
+                        }
+
+                        // not clamped, so faster speeds set in the debug settings are kept
+                        let mut speed = time.relative_speed_f64();
+                        let slider = egui::Slider::new(&mut speed, 0.0..=2.0)
+                            .clamp_to_range(false)
+                            .max_decimals(2);
+                        if ui.add(slider).on_hover_text("Game speed").changed() {
+                            time.set_relative_speed_f64(speed);
+                        }
+                    }
+                    if step_frame {
+                        *world.resource_mut::<FrameStep>() = FrameStep::Requested;
+                    }
+
+                    ui.menu_button("Theme", |ui| {
+                        let mut config = world.resource_mut::<EditorConfig>();
+                        for &theme in EditorTheme::ALL {
+                            let selected = config.theme == Some(theme);
+                            if ui.radio(selected, theme.to_string()).clicked() {
+                                config.theme = Some(theme);
+                                ui.close_menu();
+                            }
+                        }
+                    });
+
+                    ui.menu_button("Open window", |ui| {
+                        // categories in the order their first window was added
+                        let mut categories: IndexMap<&str, Vec<&EditorWindowData>> =
+                            IndexMap::default();
+                        for window in self.windows.values() {
+                            categories.entry(window.category).or_default().push(window);
+                        }
+
+                        for (category, windows) in categories {
+                            ui.menu_button(category, |ui| {
+                                for window in windows {
+                                    let cx = EditorWindowContext {
+                                        window_states: &mut self.window_states,
+                                        internal_state,
+                                    };
+                                    (window.menu_ui_fn)(world, cx, ui);
+                                }
+                            });
+                        }
                     });
+                })
+                .response;
+                // .interact(egui::Sense::click());
+
+                let zoom = ui.input(|input| input.zoom_delta());
+                if zoom != 1.0 && ui.rect_contains_pointer(bar_response.rect) {
+                    let mut config = world.resource_mut::<EditorConfig>();
+                    let scale = config.ui_scale.unwrap_or(1.0) * zoom;
+                    config.ui_scale = Some(scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE));
                 }
 
-                ui.menu_button("Open window", |ui| {
-                    for (&_, window) in self.windows.iter() {
-                        let cx = EditorWindowContext {
-                            window_states: &mut self.window_states,
-                            internal_state,
-                        };
-                        (window.menu_ui_fn)(world, cx, ui);
+                if bar_response.double_clicked() {
+                    let mut window = world
+                        .query::<&mut Window>()
+                        .get_mut(world, self.on_window)
+                        .unwrap();
+
+                    match window.mode {
+                        WindowMode::Windowed => window.mode = WindowMode::BorderlessFullscreen,
+                        _ => window.mode = WindowMode::Windowed,
                     }
-                });
-            })
-            .response;
-            // .interact(egui::Sense::click());
-
-            if bar_response.double_clicked() {
-                let mut window = world
-                    .query::<&mut Window>()
-                    .get_mut(world, self.on_window)
-                    .unwrap();
-
-                match window.mode {
-                    WindowMode::Windowed => window.mode = WindowMode::BorderlessFullscreen,
-                    _ => window.mode = WindowMode::Windowed,
                 }
-            }
-        });
+            });
     }
 
     fn editor_window_inner(
@@ -418,17 +1326,30 @@ impl Editor {
         internal_state: &mut EditorInternalState,
         tab: TreeTab,
     ) {
-        if ui.button("Pop out").clicked() {
-            if let TreeTab::CustomWindow(window) = tab {
+        if let TreeTab::CustomWindow(window) = tab {
+            let floating = internal_state.has_floating_window_by_id(window);
+            let pop_out = ui
+                .add_enabled(!floating, egui::Button::new("Pop out"))
+                .on_disabled_hover_text("Already open as a floating window");
+            if pop_out.clicked() {
                 let id = internal_state.next_floating_window_id();
                 internal_state.floating_windows.push(FloatingWindow {
                     window,
                     id,
                     initial_position: None,
                 });
+                ui.close_menu();
+            }
+            let pinned = internal_state.pinned.contains(&window);
+            if ui.button(if pinned { "Unpin" } else { "Pin" }).clicked() {
+                internal_state.set_pinned_by_id(window, !pinned);
+                ui.close_menu();
             }
 
-            ui.close_menu();
+            if ui.button("Close others").clicked() {
+                internal_state.close_others_than = Some(window);
+                ui.close_menu();
+            }
         }
     }
 
@@ -439,11 +1360,12 @@ impl Editor {
         internal_state: &mut EditorInternalState,
     ) {
         let mut close_floating_windows = Vec::new();
+        let mut dock_floating_windows = Vec::new();
         let floating_windows = internal_state.floating_windows.clone();
 
         for (i, floating_window) in floating_windows.into_iter().enumerate() {
             let id = egui::Id::new(floating_window.id);
-            let title = self.windows[&floating_window.window].name;
+            let title = self.window_title(floating_window.window);
 
             let mut open = true;
             let default_size = self.windows[&floating_window.window].default_size;
@@ -456,6 +1378,17 @@ impl Editor {
                 window = window.default_pos(initial_position - egui::Vec2::new(10.0, 10.0))
             }
             window.show(ctx, |ui| {
+                let dock = ui
+                    .with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+                        ui.small_button("Dock")
+                            .on_hover_text("Move the window back into the dock area")
+                            .clicked()
+                    })
+                    .inner;
+                if dock {
+                    dock_floating_windows.push(i);
+                }
+
                 self.editor_window_inner(world, internal_state, floating_window.window, ui);
                 let desired_size = (ui.available_size() - (5.0, 5.0).into()).max((0.0, 0.0).into());
                 ui.allocate_space(desired_size);
@@ -466,8 +1399,19 @@ impl Editor {
             }
         }
 
+        close_floating_windows.extend(&dock_floating_windows);
+        close_floating_windows.sort_unstable();
+        close_floating_windows.dedup();
         for &to_remove in close_floating_windows.iter().rev() {
-            let _floating_window = internal_state.floating_windows.swap_remove(to_remove);
+            let floating_window = internal_state.floating_windows.swap_remove(to_remove);
+
+            // docking a window which already has a tab only closes the floating copy
+            let tab = TreeTab::CustomWindow(floating_window.window);
+            if dock_floating_windows.contains(&to_remove)
+                && internal_state.state.find_tab(&tab).is_none()
+            {
+                internal_state.state.push_to_focused_leaf(tab);
+            }
         }
     }
 
@@ -477,7 +1421,11 @@ impl Editor {
         ui: &mut egui::Ui,
         internal_state: &mut EditorInternalState,
     ) {
-        for (_, window) in self.windows.iter() {
+        // stable, so windows with the same order stay in insertion order
+        let mut windows: Vec<&EditorWindowData> = self.windows.values().collect();
+        windows.sort_by_key(|window| window.viewport_toolbar_order);
+
+        for window in windows {
             let cx = EditorWindowContext {
                 window_states: &mut self.window_states,
                 internal_state,
@@ -508,7 +1456,30 @@ struct TabViewer<'a> {
     editor: &'a mut Editor,
     internal_state: &'a mut EditorInternalState,
     world: &'a mut World,
+    panel_styles: &'a PanelStyles,
+    /// The whole style of the docked panels, only set if there is an [`EditorStyle`]
+    docked_style: Option<&'a egui::Style>,
 }
+impl TabViewer<'_> {
+    fn viewport_context_menu(&mut self, ui: &mut egui::Ui, rect: egui::Rect) {
+        let id = ui.id().with("viewport_context_menu");
+        let response = ui.interact(rect, id, egui::Sense::click());
+        if response.secondary_clicked() {
+            self.editor.context_menu_pos = response.interact_pointer_pos();
+        }
+
+        response.context_menu(|ui| {
+            let context_menu = self.world.resource::<EditorViewportContextMenu>();
+            for (i, label) in context_menu.labels().enumerate() {
+                if ui.button(label).clicked() {
+                    self.editor.context_menu_action = Some(i);
+                    ui.close_menu();
+                }
+            }
+        });
+    }
+}
+
 impl egui_dock::TabViewer for TabViewer<'_> {
     type Tab = TreeTab;
 
@@ -517,21 +1488,39 @@ impl egui_dock::TabViewer for TabViewer<'_> {
             TreeTab::GameView => {
                 let viewport = ui.clip_rect();
 
-                ui.horizontal(|ui| {
-                    ui.style_mut().spacing.button_padding = egui::vec2(2.0, 0.0);
-                    let height = ui.spacing().interact_size.y;
-                    ui.set_min_size(egui::vec2(ui.available_width(), height));
+                let toolbar_visuals = self.panel_styles.viewport_toolbar.as_ref();
+                let toolbar_frame = match toolbar_visuals {
+                    Some(visuals) => egui::Frame::none().fill(visuals.panel_fill),
+                    None => egui::Frame::none(),
+                };
+                let toolbar = toolbar_frame.show(ui, |ui| {
+                    if let Some(visuals) = toolbar_visuals {
+                        *ui.visuals_mut() = visuals.clone();
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.style_mut().spacing.button_padding = egui::vec2(2.0, 0.0);
+                        let height = ui.spacing().interact_size.y;
+                        ui.set_min_size(egui::vec2(ui.available_width(), height));
 
-                    self.editor
-                        .editor_viewport_toolbar_ui(self.world, ui, self.internal_state);
+                        self.editor
+                            .editor_viewport_toolbar_ui(self.world, ui, self.internal_state);
+                    });
                 });
 
                 self.editor.viewport = viewport;
 
+                self.viewport_context_menu(ui, viewport.with_min_y(toolbar.response.rect.max.y));
+
                 self.editor
                     .editor_viewport_ui(self.world, ui, self.internal_state);
             }
             TreeTab::CustomWindow(window_id) => {
+                if let Some(style) = self.docked_style {
+                    ui.set_style(style.clone());
+                } else if let Some(visuals) = &self.panel_styles.docked_panels {
+                    *ui.visuals_mut() = visuals.clone();
+                }
                 self.editor
                     .editor_window_inner(self.world, self.internal_state, window_id, ui);
             }
@@ -551,22 +1540,201 @@ impl egui_dock::TabViewer for TabViewer<'_> {
 
     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
         match *tab {
-            TreeTab::GameView => "Viewport".into(),
+            TreeTab::GameView => GAME_VIEW_TAB_NAME.into(),
             TreeTab::CustomWindow(window_id) => {
-                self.editor.windows.get(&window_id).unwrap().name.into()
+                let name = self.editor.window_title(window_id);
+                match self.internal_state.pinned.contains(&window_id) {
+                    true => format!("📌 {name}").into(),
+                    false => name.into(),
+                }
             }
         }
     }
 
+    // titles can change with the window state, so they can't identify the tab
+    fn id(&mut self, tab: &mut Self::Tab) -> egui::Id {
+        egui::Id::new(*tab)
+    }
+
+    fn closeable(&mut self, tab: &mut Self::Tab) -> bool {
+        match *tab {
+            TreeTab::GameView => false,
+            TreeTab::CustomWindow(window_id) => !self.internal_state.pinned.contains(&window_id),
+        }
+    }
+
     fn clear_background(&self, tab: &Self::Tab) -> bool {
         !matches!(tab, TreeTab::GameView)
     }
 }
 
-fn play_pause_button(active: bool, ui: &mut egui::Ui) -> egui::Response {
+/// Replaces the visuals once their dark mode doesn't match the [`EditorConfig::theme`],
+/// so tweaks made to them on top of the theme are kept
+fn apply_theme(world: &World, ctx: &egui::Context, window: Entity) {
+    let Some(theme) = world.resource::<EditorConfig>().theme else {
+        return;
+    };
+    let dark = match theme {
+        EditorTheme::Dark => true,
+        EditorTheme::Light => false,
+        EditorTheme::FollowSystem => world
+            .get::<Window>(window)
+            .and_then(|window| window.window_theme)
+            .is_none_or(|theme| theme == WindowTheme::Dark),
+    };
+
+    if ctx.style().visuals.dark_mode != dark {
+        let visuals = if dark {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        ctx.set_visuals(visuals);
+    }
+}
+
+fn apply_ui_scale(world: &mut World) {
+    let Some(ui_scale) = world.resource::<EditorConfig>().ui_scale else {
+        return;
+    };
+    if let Some(mut egui_settings) = world.get_resource_mut::<EguiSettings>() {
+        if egui_settings.scale_factor != ui_scale {
+            egui_settings.scale_factor = ui_scale;
+        }
+    }
+}
+
+/// Fills the remaining screen area around `viewport`, so the game doesn't show through gaps between panels.
+fn paint_background_outside_viewport(
+    ctx: &egui::Context,
+    viewport: egui::Rect,
+    fill: egui::Color32,
+) {
+    let area = ctx.available_rect();
+    let painter = ctx.layer_painter(egui::LayerId::background());
+
+    let viewport = viewport.intersect(area);
+    if !viewport.is_positive() {
+        painter.rect_filled(area, 0.0, fill);
+        return;
+    }
+
+    let above = egui::Rect::from_min_max(area.min, egui::pos2(area.max.x, viewport.min.y));
+    let below = egui::Rect::from_min_max(egui::pos2(area.min.x, viewport.max.y), area.max);
+    let left = egui::Rect::from_min_max(
+        egui::pos2(area.min.x, viewport.min.y),
+        egui::pos2(viewport.min.x, viewport.max.y),
+    );
+    let right = egui::Rect::from_min_max(
+        egui::pos2(viewport.max.x, viewport.min.y),
+        egui::pos2(area.max.x, viewport.max.y),
+    );
+    for rect in [above, below, left, right] {
+        if rect.is_positive() {
+            painter.rect_filled(rect, 0.0, fill);
+        }
+    }
+}
+
+fn play_pause_button(active: bool, shortcut: Option<&str>, ui: &mut egui::Ui) -> egui::Response {
     let icon = match active {
         true => "▶",
         false => "⏸",
     };
-    ui.add(egui::Button::new(icon).frame(false))
+    let response = ui.add(egui::Button::new(icon).frame(false));
+    match shortcut {
+        Some(shortcut) => response.on_hover_text(format!("Toggle editor ({shortcut})")),
+        None => response,
+    }
+}
+
+/// Pauses the game's virtual time, independent of whether the editor is active
+fn game_pause_button(paused: bool, shortcut: Option<&str>, ui: &mut egui::Ui) -> egui::Response {
+    let hover_text = match paused {
+        true => "Resume game time",
+        false => "Pause game time",
+    };
+    let hover_text = match shortcut {
+        Some(shortcut) => format!("{hover_text} ({shortcut})"),
+        None => hover_text.to_owned(),
+    };
+    ui.selectable_label(paused, "⏸ Game")
+        .on_hover_text(hover_text)
+}
+
+fn frame_step_button(paused: bool, editor_active: bool, ui: &mut egui::Ui) -> egui::Response {
+    let disabled_hover_text = if editor_active {
+        "Pause game time to step single frames"
+    } else {
+        "Open the editor to step single frames"
+    };
+    ui.add_enabled(
+        paused && editor_active,
+        egui::Button::new("⏭ Step").frame(false),
+    )
+    .on_hover_text("Advance the game by one frame")
+    .on_disabled_hover_text(disabled_hover_text)
+}
+
+/// Progress of advancing the paused game by a single frame from the menu bar.
+///
+/// The step button unpauses [`Time<Virtual>`], the next frame runs with a regular delta,
+/// and time is paused again at the end of that frame.
+#[derive(Resource, Default, PartialEq, Eq)]
+pub(crate) enum FrameStep {
+    #[default]
+    Idle,
+    Requested,
+    Stepping,
+}
+
+/// Marks the frame which advanced the virtual time after a step was requested
+pub(crate) fn begin_frame_step(mut frame_step: ResMut<FrameStep>) {
+    if *frame_step == FrameStep::Requested {
+        *frame_step = FrameStep::Stepping;
+    }
+}
+
+pub(crate) fn end_frame_step(
+    mut frame_step: ResMut<FrameStep>,
+    time: Option<ResMut<Time<Virtual>>>,
+) {
+    if *frame_step == FrameStep::Stepping {
+        if let Some(mut time) = time {
+            time.pause();
+        }
+        *frame_step = FrameStep::Idle;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_to_primary_window_when_window_is_despawned() {
+        let mut world = World::new();
+        world.init_resource::<Events<EditorEvent>>();
+        world.init_resource::<EditorConfig>();
+        let primary_window = world.spawn((Window::default(), PrimaryWindow)).id();
+        let window = world.spawn(Window::default()).id();
+        world.insert_resource(Editor::new(window, true));
+
+        world.despawn(window);
+        Editor::system(&mut world);
+
+        let editor = world.resource::<Editor>();
+        assert_eq!(editor.window(), primary_window);
+        assert!(!editor.active());
+        assert!(!editor.always_active());
+
+        let events: Vec<_> = world
+            .resource_mut::<Events<EditorEvent>>()
+            .drain()
+            .collect();
+        assert!(matches!(
+            events[..],
+            [EditorEvent::Toggle { now_active: false }]
+        ));
+    }
 }
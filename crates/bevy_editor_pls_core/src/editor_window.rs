@@ -1,24 +1,39 @@
-use bevy::prelude::{App, World};
+use bevy::prelude::{App, Handle, Image, World};
 use bevy::utils::HashMap;
 use bevy_inspector_egui::egui;
 use std::any::{Any, TypeId};
 
-use crate::editor::EditorWindowState;
+use crate::editor::{EditorWindowState, ShortcutHints};
 
 /// An editor window type
 pub trait EditorWindow: 'static {
     type State: Default + Any + Send + Sync;
 
     const NAME: &'static str;
+    /// Submenu of the `Open window` menu the window is listed in
+    const CATEGORY: &'static str = "General";
+    /// Glyph shown before the name in the `Open window` menu and in tab titles, like `"🔍"`
+    const ICON: Option<&'static str> = None;
     const DEFAULT_SIZE: (f32, f32) = (0.0, 0.0);
 
     fn ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui);
 
-    /// Ui shown in the `Open Window` menu item. By default opens the window as a floating window.
+    /// Ui shown in the `Open Window` menu item. By default opens the window as a floating window,
+    /// showing the window's entry in [`ShortcutHints`] on the right.
     fn menu_ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
-        let _ = world;
+        let label = match Self::ICON {
+            Some(icon) => format!("{icon} {}", Self::NAME),
+            None => Self::NAME.to_owned(),
+        };
+        let mut button = egui::Button::new(label);
+        if let Some(shortcut) = world
+            .get_resource::<ShortcutHints>()
+            .and_then(ShortcutHints::window::<Self>)
+        {
+            button = button.shortcut_text(shortcut);
+        }
 
-        if ui.button(Self::NAME).clicked() {
+        if ui.add(button).clicked() {
             cx.open_floating_window::<Self>();
             ui.close_menu();
         }
@@ -27,11 +42,45 @@ pub trait EditorWindow: 'static {
     fn viewport_toolbar_ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
         let _ = (world, cx, ui);
     }
+    /// Windows with a lower order show their [`EditorWindow::viewport_toolbar_ui`] further left.
+    /// Windows with the same order are shown in the order they were added.
+    fn viewport_toolbar_order() -> i32 {
+        0
+    }
     /// Ui shown on top of the game viewport.
     fn viewport_ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
         let _ = (world, cx, ui);
     }
 
+    /// Title shown instead of [`EditorWindow::NAME`], for example to show what the window is focused on.
+    fn title(state: &Self::State) -> Option<String> {
+        let _ = state;
+        None
+    }
+
+    /// Returns the parts of the state that should survive restarts, serialized as RON.
+    /// Windows returning `None` (the default) only keep their state for the current session.
+    fn save_state(state: &Self::State) -> Option<String> {
+        let _ = state;
+        None
+    }
+    /// Restores the state previously returned from [`EditorWindow::save_state`].
+    fn load_state(state: &mut Self::State, saved: &str) {
+        let _ = (state, saved);
+    }
+
+    /// Called after the window was docked or opened as a floating window while it wasn't open anywhere,
+    /// like when the editor starts with it in the layout. The [`Editor`](crate::Editor) resource
+    /// isn't available in the world while this runs.
+    fn on_open(world: &mut World, state: &mut Self::State) {
+        let _ = (world, state);
+    }
+    /// Called after the last tab or floating window of the window was closed.
+    /// Like [`EditorWindow::on_open`], this runs without the [`Editor`](crate::Editor) resource.
+    fn on_close(world: &mut World, state: &mut Self::State) {
+        let _ = (world, state);
+    }
+
     /// Necessary setup (resources, systems) for the window.
     fn app_setup(app: &mut App) {
         let _ = app;
@@ -96,6 +145,18 @@ impl EditorWindowContext<'_> {
         Some((a, b))
     }
 
+    pub fn internal_state(&self) -> &crate::editor::EditorInternalState {
+        self.internal_state
+    }
+
+    /// Registers the image with egui and returns its texture id, e.g. for showing it with [`egui::Image`].
+    ///
+    /// Textures are cached across frames and released once they haven't been requested for a while.
+    /// Images egui can't display correctly, like single-channel ones, are converted to RGBA first.
+    pub fn image_texture(&mut self, world: &mut World, image: &Handle<Image>) -> egui::TextureId {
+        self.internal_state.textures.texture_id(world, image)
+    }
+
     pub fn open_floating_window<W: ?Sized + EditorWindow>(&mut self) {
         let floating_window_id = self.internal_state.next_floating_window_id();
         let window_id = std::any::TypeId::of::<W>();
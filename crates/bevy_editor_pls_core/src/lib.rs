@@ -2,21 +2,37 @@
 pub mod editor;
 /// Trait definition for new editor windows
 pub mod editor_window;
+/// Named entity selections shared between windows and tools
+pub mod selection;
+/// Undo and redo of edits made through the editor
+pub mod undo;
+/// Extensible right-click menu of the viewport
+pub mod viewport_context_menu;
+
+mod persistence;
+mod textures;
 
 use std::marker::PhantomData;
 
 use bevy::prelude::*;
 use bevy::render::camera::CameraUpdateSystem;
+use bevy::time::TimeSystem;
 use bevy::transform::TransformSystem;
 use bevy::window::{PrimaryWindow, WindowRef};
 use bevy_inspector_egui::{
     bevy_egui::{EguiPlugin, EguiSet},
     DefaultInspectorConfigPlugin,
 };
-use editor::EditorInternalState;
+use editor::{EditorInternalState, FrameStep};
 use editor_window::EditorWindow;
 
-pub use editor::{Editor, EditorEvent};
+pub use editor::{
+    Editor, EditorConfig, EditorEvent, EditorModifiers, EditorPointerState, EditorStyle,
+    EditorTheme, PanelStyles, ShortcutHints,
+};
+pub use selection::{Selection, SelectionSet, SelectionSlotChanged};
+pub use undo::{EditorCommand, UndoStack};
+pub use viewport_context_menu::EditorViewportContextMenu;
 
 /// Re-export of [`egui_dock`]
 pub use egui_dock;
@@ -41,6 +57,10 @@ impl AddEditorWindow for App {
     fn add_editor_window<W: EditorWindow>(&mut self) -> &mut Self {
         let mut editor = self.world_mut().get_resource_mut::<Editor>().expect("Editor resource missing. Make sure to add the `EditorPlugin` before calling `app.add_editor_window`.");
         editor.add_window::<W>();
+        self.world_mut()
+            .resource_mut::<EditorInternalState>()
+            .window_names
+            .insert(std::any::TypeId::of::<W>(), W::NAME);
 
         self.add_plugins(WindowSetupPlugin::<W>(PhantomData));
 
@@ -56,6 +76,8 @@ pub enum EditorSet {
 
 pub struct EditorPlugin {
     pub window: WindowRef,
+    /// See [`Editor::passive_input`]
+    pub passive_input: bool,
 }
 impl Plugin for EditorPlugin {
     fn build(&self, app: &mut App) {
@@ -71,15 +93,26 @@ impl Plugin for EditorPlugin {
                 let entity = app
                     .world_mut()
                     .query_filtered::<Entity, With<PrimaryWindow>>()
-                    .single(&app.world());
+                    .single(app.world());
                 (entity, false)
             }
             WindowRef::Entity(entity) => (entity, true),
         };
 
-        app.insert_resource(Editor::new(window_entity, always_active))
+        let mut editor = Editor::new(window_entity, always_active);
+        editor.set_passive_input(self.passive_input);
+
+        app.insert_resource(editor)
             .init_resource::<EditorInternalState>()
+            .init_resource::<EditorConfig>()
+            .init_resource::<EditorViewportContextMenu>()
+            .init_resource::<ShortcutHints>()
+            .init_resource::<FrameStep>()
+            .init_resource::<Selection>()
+            .init_resource::<SelectionSet>()
+            .init_resource::<UndoStack>()
             .add_event::<EditorEvent>()
+            .add_event::<SelectionSlotChanged>()
             .configure_sets(PostUpdate, EditorSet::UI)
             .add_systems(
                 Update,
@@ -88,6 +121,32 @@ impl Plugin for EditorPlugin {
                     .before(TransformSystem::TransformPropagate)
                     .before(CameraUpdateSystem)
                     .before(EguiSet::ProcessOutput),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    selection::remove_despawned_entities,
+                    selection::sync_primary_slot,
+                )
+                    .chain(),
+            )
+            .add_systems(First, editor::begin_frame_step.after(TimeSystem))
+            .add_systems(
+                Startup,
+                (
+                    persistence::load_settings,
+                    persistence::load_window_states,
+                    persistence::load_layout,
+                ),
+            )
+            .add_systems(
+                Last,
+                (
+                    selection::send_selection_slot_events,
+                    selection::send_selection_changed,
+                    persistence::save_on_exit,
+                    editor::end_frame_step,
+                ),
             );
     }
 }
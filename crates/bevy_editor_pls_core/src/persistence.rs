@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::editor::{EditorInternalState, EditorTheme, MAX_UI_SCALE, MIN_UI_SCALE};
+use crate::{Editor, EditorConfig};
+
+/// The parts of the [`EditorConfig`] which can be changed in the editor UI
+#[derive(Serialize, Deserialize)]
+struct SavedEditorSettings {
+    #[serde(default)]
+    theme: Option<EditorTheme>,
+    #[serde(default)]
+    ui_scale: Option<f32>,
+}
+
+/// Settings saved in a previous session replace the configured ones
+pub(crate) fn load_settings(mut config: ResMut<EditorConfig>) {
+    let Some(path) = config.settings_path.clone() else {
+        return;
+    };
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!(
+                "failed to read editor settings from {}: {error}",
+                path.display()
+            );
+            return;
+        }
+    };
+
+    match ron::from_str::<SavedEditorSettings>(&contents) {
+        Ok(saved) => {
+            if saved.theme.is_some() {
+                config.theme = saved.theme;
+            }
+            if let Some(ui_scale) = saved.ui_scale {
+                config.ui_scale = Some(ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE));
+            }
+        }
+        Err(error) => warn!(
+            "failed to parse editor settings in {}: {error}",
+            path.display()
+        ),
+    }
+}
+
+pub(crate) fn load_window_states(mut editor: ResMut<Editor>, config: Res<EditorConfig>) {
+    let Some(path) = &config.window_state_path else {
+        return;
+    };
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!(
+                "failed to read editor window state from {}: {error}",
+                path.display()
+            );
+            return;
+        }
+    };
+
+    match ron::from_str::<BTreeMap<String, String>>(&contents) {
+        Ok(saved) => editor.load_window_states(&saved),
+        Err(error) => warn!(
+            "failed to parse editor window state in {}: {error}",
+            path.display()
+        ),
+    }
+}
+
+/// Replaces the default layout with the saved one, if there is one which only contains known windows
+pub(crate) fn load_layout(
+    mut internal_state: ResMut<EditorInternalState>,
+    config: Res<EditorConfig>,
+) {
+    let Some(path) = &config.layout_path else {
+        return;
+    };
+    match internal_state.load_layout(path) {
+        Ok(()) => {}
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => warn!(
+            "failed to load editor layout from {}, using the default layout: {error}",
+            path.display()
+        ),
+    }
+}
+
+pub(crate) fn save_on_exit(
+    mut app_exit: EventReader<AppExit>,
+    editor: Res<Editor>,
+    internal_state: Res<EditorInternalState>,
+    config: Res<EditorConfig>,
+) {
+    if app_exit.is_empty() {
+        return;
+    }
+    app_exit.clear();
+
+    if let Some(path) = &config.layout_path {
+        let result = create_parent_folder(path).and_then(|()| internal_state.save_layout(path));
+        if let Err(error) = result {
+            warn!(
+                "failed to save editor layout to {}: {error}",
+                path.display()
+            );
+        }
+    }
+
+    if let Some(path) = &config.settings_path {
+        let saved = SavedEditorSettings {
+            theme: config.theme,
+            ui_scale: config.ui_scale,
+        };
+        let result = ron::ser::to_string_pretty(&saved, ron::ser::PrettyConfig::default())
+            .map_err(|error| error.to_string())
+            .and_then(|ron| write_file(path, ron).map_err(|error| error.to_string()));
+        if let Err(error) = result {
+            warn!(
+                "failed to save editor settings to {}: {error}",
+                path.display()
+            );
+        }
+    }
+
+    let Some(path) = &config.window_state_path else {
+        return;
+    };
+    let saved = editor.save_window_states();
+    let result = ron::ser::to_string_pretty(&saved, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|ron| write_file(path, ron).map_err(|error| error.to_string()));
+    if let Err(error) = result {
+        warn!(
+            "failed to save editor window state to {}: {error}",
+            path.display()
+        );
+    }
+}
+
+fn create_parent_folder(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(folder) => std::fs::create_dir_all(folder),
+        None => Ok(()),
+    }
+}
+
+/// Writes the file, creating the folder it is in first
+fn write_file(path: &Path, contents: String) -> std::io::Result<()> {
+    create_parent_folder(path)?;
+    std::fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saving_creates_the_folder() {
+        let folder =
+            std::env::temp_dir().join(format!("bevy_editor_pls_test_{}", std::process::id()));
+        let path = folder.join("nested").join("editor_settings.ron");
+
+        write_file(&path, "()".to_owned()).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "()");
+
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+use std::borrow::Cow;
+
+use bevy::{ecs::entity::Entities, prelude::*, utils::HashMap};
+use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
+
+use crate::EditorEvent;
+
+/// The entities selected in the editor, shared by the hierarchy, the inspector and the viewport tools.
+///
+/// Game code can read it to react to the selection, or change it to select entities from elsewhere.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct Selection {
+    /// In the order they were selected
+    pub selected: Vec<Entity>,
+}
+
+impl Selection {
+    /// Replaces the selection with just this entity
+    pub fn select(&mut self, entity: Entity) {
+        self.selected.clear();
+        self.selected.push(entity);
+    }
+
+    /// Adds the entity to the selection, or removes it if it is already selected
+    pub fn toggle(&mut self, entity: Entity) {
+        match self
+            .selected
+            .iter()
+            .position(|&selected| selected == entity)
+        {
+            Some(index) => {
+                self.selected.remove(index);
+            }
+            None => self.selected.push(entity),
+        }
+    }
+
+    /// Adds the entity to the selection, if it isn't selected yet
+    pub fn add(&mut self, entity: Entity) {
+        if !self.contains(entity) {
+            self.selected.push(entity);
+        }
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        self.selected.retain(|&selected| selected != entity);
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.selected.contains(&entity)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.selected.iter().copied()
+    }
+
+    /// The most recently selected entity
+    pub fn last(&self) -> Option<Entity> {
+        self.selected.last().copied()
+    }
+}
+
+pub(crate) fn remove_despawned_entities(mut selection: ResMut<Selection>, entities: &Entities) {
+    if selection.iter().any(|entity| !entities.contains(entity)) {
+        selection
+            .selected
+            .retain(|&entity| entities.contains(entity));
+    }
+}
+
+/// Mirrors the [`Selection`] into the [`SelectionSet::PRIMARY`] slot.
+/// Whichever side changed since the last sync wins, so tools can select entities through the slot.
+pub(crate) fn sync_primary_slot(
+    mut selection: ResMut<Selection>,
+    mut selection_set: ResMut<SelectionSet>,
+    mut synced: Local<Vec<Entity>>,
+) {
+    let primary = selection_set.get_mut(SelectionSet::PRIMARY);
+
+    if selection.selected != *synced {
+        primary.clear();
+        for entity in selection.iter() {
+            primary.select_maybe_add(entity, true);
+        }
+    } else if primary.as_slice() != synced.as_slice() {
+        selection.selected = primary.iter().collect();
+    } else {
+        return;
+    }
+
+    synced.clone_from(&selection.selected);
+}
+
+pub(crate) fn send_selection_changed(
+    selection: Res<Selection>,
+    mut last_sent: Local<Vec<Entity>>,
+    mut editor_events: EventWriter<EditorEvent>,
+) {
+    if !selection.is_changed() || selection.selected == *last_sent {
+        return;
+    }
+    last_sent.clone_from(&selection.selected);
+    editor_events.send(EditorEvent::SelectionChanged {
+        selected: selection.selected.clone(),
+    });
+}
+
+/// Named selections, so tools can operate on their own set of entities without fighting over
+/// the selection shown in the hierarchy.
+///
+/// The [`SelectionSet::PRIMARY`] slot is kept in sync with the [`Selection`].
+/// Every other slot is up to the tools using it.
+#[derive(Resource, Default)]
+pub struct SelectionSet {
+    slots: HashMap<Cow<'static, str>, SelectedEntities>,
+    /// Contents of each slot when [`SelectionSlotChanged`] events were last sent
+    last_sent: HashMap<Cow<'static, str>, Vec<Entity>>,
+}
+
+impl SelectionSet {
+    pub const PRIMARY: &'static str = "primary";
+
+    pub fn get(&self, slot: &str) -> Option<&SelectedEntities> {
+        self.slots.get(slot)
+    }
+
+    /// Returns the slot, creating an empty one if it doesn't exist yet
+    pub fn get_mut(&mut self, slot: impl Into<Cow<'static, str>>) -> &mut SelectedEntities {
+        self.slots.entry(slot.into()).or_default()
+    }
+
+    pub fn remove(&mut self, slot: &str) -> Option<SelectedEntities> {
+        self.slots.remove(slot)
+    }
+
+    pub fn primary(&self) -> Option<&SelectedEntities> {
+        self.get(SelectionSet::PRIMARY)
+    }
+
+    pub fn slots(&self) -> impl Iterator<Item = (&str, &SelectedEntities)> {
+        self.slots.iter().map(|(name, slot)| (name.as_ref(), slot))
+    }
+}
+
+/// Sent whenever the entities of a [`SelectionSet`] slot change, including when the slot is removed
+#[derive(Event, Clone, Debug)]
+pub struct SelectionSlotChanged {
+    pub slot: Cow<'static, str>,
+}
+
+pub(crate) fn send_selection_slot_events(
+    mut selection: ResMut<SelectionSet>,
+    mut events: EventWriter<SelectionSlotChanged>,
+) {
+    let selection = &mut *selection;
+
+    for (name, slot) in &selection.slots {
+        let changed = selection
+            .last_sent
+            .get(name)
+            .map_or(!slot.is_empty(), |last| last.as_slice() != slot.as_slice());
+        if changed {
+            selection
+                .last_sent
+                .insert(name.clone(), slot.as_slice().to_vec());
+            events.send(SelectionSlotChanged { slot: name.clone() });
+        }
+    }
+
+    let slots = &selection.slots;
+    selection.last_sent.retain(|name, _| {
+        let exists = slots.contains_key(name);
+        if !exists {
+            events.send(SelectionSlotChanged { slot: name.clone() });
+        }
+        exists
+    });
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::utils::HashMap;
+use bevy_inspector_egui::bevy_egui::{egui, EguiUserTextures};
+
+/// How many editor frames a texture may go unused before it is released
+const RELEASE_AFTER_UNUSED_FRAMES: u32 = 60;
+
+/// Egui textures of images shown by editor windows, see [`EditorWindowContext::image_texture`](crate::editor_window::EditorWindowContext::image_texture)
+#[derive(Default)]
+pub(crate) struct EditorTextures {
+    textures: HashMap<AssetId<Image>, RegisteredTexture>,
+    frame: u32,
+}
+
+struct RegisteredTexture {
+    /// The image registered with egui. A converted copy if the original can't be displayed as is.
+    handle: Handle<Image>,
+    texture_id: egui::TextureId,
+    last_used: u32,
+    /// Whether the image was loaded yet, so its format could be checked
+    format_checked: bool,
+}
+
+impl EditorTextures {
+    pub(crate) fn texture_id(
+        &mut self,
+        world: &mut World,
+        image: &Handle<Image>,
+    ) -> egui::TextureId {
+        if let Some(texture) = self.textures.get_mut(&image.id()) {
+            texture.last_used = self.frame;
+            if texture.format_checked {
+                return texture.texture_id;
+            }
+        }
+
+        let (handle, format_checked) = displayable_image(world, image);
+        let mut user_textures = world.resource_mut::<EguiUserTextures>();
+        if let Some(previous) = self.textures.get(&image.id()) {
+            if previous.handle != handle {
+                user_textures.remove_image(&previous.handle);
+            }
+        }
+        let texture_id = user_textures.add_image(handle.clone());
+
+        self.textures.insert(
+            image.id(),
+            RegisteredTexture {
+                handle,
+                texture_id,
+                last_used: self.frame,
+                format_checked,
+            },
+        );
+        texture_id
+    }
+
+    /// Releases the textures which weren't requested for [`RELEASE_AFTER_UNUSED_FRAMES`]
+    pub(crate) fn release_unused(&mut self, world: &mut World) {
+        self.frame = self.frame.wrapping_add(1);
+
+        let Some(mut user_textures) = world.get_resource_mut::<EguiUserTextures>() else {
+            return;
+        };
+        let frame = self.frame;
+        self.textures.retain(|_, texture| {
+            let used = frame.wrapping_sub(texture.last_used) <= RELEASE_AFTER_UNUSED_FRAMES;
+            if !used {
+                user_textures.remove_image(&texture.handle);
+            }
+            used
+        });
+    }
+}
+
+/// Returns the image, or a converted copy of it if egui would only show some of its channels.
+/// The second value is `false` if the image isn't loaded yet, so its format is unknown.
+fn displayable_image(world: &mut World, image: &Handle<Image>) -> (Handle<Image>, bool) {
+    let mut images = world.resource_mut::<Assets<Image>>();
+    let Some(loaded) = images.get(image) else {
+        return (image.clone(), false);
+    };
+
+    let needs_conversion = matches!(
+        loaded.texture_descriptor.format,
+        TextureFormat::R8Unorm | TextureFormat::Rg8Unorm
+    );
+    if !needs_conversion {
+        return (image.clone(), true);
+    }
+
+    match loaded.convert(TextureFormat::Rgba8UnormSrgb) {
+        Some(converted) => (images.add(converted), true),
+        None => (image.clone(), true),
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+use crate::Selection;
+
+/// How many edits the [`UndoStack`] remembers before dropping the oldest ones
+pub const MAX_UNDO_STEPS: usize = 100;
+
+/// A reversible edit made through the editor, recorded in the [`UndoStack`].
+///
+/// Commands are pushed after they were applied, so [`redo`](EditorCommand::redo) only ever runs after [`undo`](EditorCommand::undo).
+pub trait EditorCommand: Send + Sync + 'static {
+    /// Shown in the edit history, like `Delete Cube`
+    fn description(&self) -> String;
+    fn undo(&mut self, world: &mut World);
+    fn redo(&mut self, world: &mut World);
+
+    /// Entities which the last `undo` or `redo` had to respawn under a different id, by their previous id
+    fn respawned_entities(&self) -> EntityHashMap<Entity> {
+        EntityHashMap::default()
+    }
+    /// Follows entities another command respawned under a different id
+    fn map_entities(&mut self, _entity_map: &EntityHashMap<Entity>) {}
+}
+
+/// Edits made through the editor which can be undone and redone, most recent last.
+///
+/// Pushing a new edit discards the edits which were undone before.
+#[derive(Resource, Default)]
+pub struct UndoStack {
+    done: VecDeque<Box<dyn EditorCommand>>,
+    undone: Vec<Box<dyn EditorCommand>>,
+}
+
+impl UndoStack {
+    /// Records an edit which was already applied to the world
+    pub fn push(&mut self, command: impl EditorCommand) {
+        self.undone.clear();
+        self.done.push_back(Box::new(command));
+        if self.done.len() > MAX_UNDO_STEPS {
+            self.done.pop_front();
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Descriptions of the edits which can be undone, oldest first
+    pub fn done(&self) -> impl Iterator<Item = String> + '_ {
+        self.done.iter().map(|command| command.description())
+    }
+    /// Descriptions of the edits which can be redone, the next one first
+    pub fn undone(&self) -> impl Iterator<Item = String> + '_ {
+        self.undone
+            .iter()
+            .rev()
+            .map(|command| command.description())
+    }
+
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+
+    /// Reverts the most recent edit. Returns `false` if there is nothing to undo.
+    pub fn undo(world: &mut World) -> bool {
+        let Some(mut command) = world.resource_mut::<UndoStack>().done.pop_back() else {
+            return false;
+        };
+        command.undo(world);
+        let respawned = command.respawned_entities();
+
+        let mut stack = world.resource_mut::<UndoStack>();
+        stack.undone.push(command);
+        stack.map_entities(&respawned);
+        map_selection(world, &respawned);
+        true
+    }
+
+    /// Applies the most recently undone edit again. Returns `false` if there is nothing to redo.
+    pub fn redo(world: &mut World) -> bool {
+        let Some(mut command) = world.resource_mut::<UndoStack>().undone.pop() else {
+            return false;
+        };
+        command.redo(world);
+        let respawned = command.respawned_entities();
+
+        let mut stack = world.resource_mut::<UndoStack>();
+        stack.done.push_back(command);
+        stack.map_entities(&respawned);
+        map_selection(world, &respawned);
+        true
+    }
+
+    fn map_entities(&mut self, entity_map: &EntityHashMap<Entity>) {
+        if entity_map.is_empty() {
+            return;
+        }
+        for command in self.done.iter_mut().chain(self.undone.iter_mut()) {
+            command.map_entities(entity_map);
+        }
+    }
+}
+
+fn map_selection(world: &mut World, entity_map: &EntityHashMap<Entity>) {
+    if entity_map.is_empty() {
+        return;
+    }
+    let mut selection = world.resource_mut::<Selection>();
+    for entity in &mut selection.selected {
+        if let Some(&respawned) = entity_map.get(entity) {
+            *entity = respawned;
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+use bevy::math::primitives::InfinitePlane3d;
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::window::PrimaryWindow;
+
+use crate::EditorEvent;
+
+type ContextMenuAction = Box<dyn Fn(&mut World, ViewportCursor) + Send + Sync + 'static>;
+
+/// Entries of the menu opened by right-clicking into the viewport.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_editor_pls_core::EditorViewportContextMenu;
+/// fn setup(mut context_menu: ResMut<EditorViewportContextMenu>) {
+///     context_menu.add("Spawn marker here", |world, cursor| {
+///         let translation = cursor.world_position().unwrap_or_default();
+///         let transform = Transform::from_translation(translation);
+///         world.spawn((Name::new("Marker"), TransformBundle::from_transform(transform)));
+///     });
+/// }
+/// ```
+#[derive(Resource)]
+pub struct EditorViewportContextMenu {
+    entries: Vec<(Cow<'static, str>, ContextMenuAction)>,
+}
+
+impl Default for EditorViewportContextMenu {
+    fn default() -> Self {
+        let mut context_menu = EditorViewportContextMenu {
+            entries: Vec::new(),
+        };
+        context_menu.add("Focus selected", |world, _| {
+            world.send_event(EditorEvent::FocusSelected);
+        });
+        context_menu.add("Frame all", |world, _| {
+            world.send_event(EditorEvent::FrameAll);
+        });
+        context_menu
+    }
+}
+
+impl EditorViewportContextMenu {
+    /// Adds an entry. The action runs after the editor UI, so it has access to the whole world.
+    pub fn add(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        action: impl Fn(&mut World, ViewportCursor) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.entries.push((label.into(), Box::new(action)));
+        self
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(label, _)| label.as_ref())
+    }
+
+    pub(crate) fn run(&self, index: usize, world: &mut World, cursor: ViewportCursor) {
+        if let Some((_, action)) = self.entries.get(index) {
+            action(world, cursor);
+        }
+    }
+}
+
+/// Where the viewport context menu was opened
+#[derive(Clone, Copy, Debug)]
+pub struct ViewportCursor {
+    /// Relative to the top left corner of the viewport, in logical pixels
+    pub viewport_position: Vec2,
+    /// Ray through the cursor from the active camera with the highest order rendering to the editor window
+    pub ray: Option<Ray3d>,
+}
+
+impl ViewportCursor {
+    pub(crate) fn new(world: &mut World, window: Entity, viewport_position: Vec2) -> Self {
+        let primary_window = world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .get_single(world)
+            .ok();
+        let ray = world
+            .query::<(&Camera, &GlobalTransform)>()
+            .iter(world)
+            .filter(|(camera, _)| {
+                let RenderTarget::Window(window_ref) = camera.target else {
+                    return false;
+                };
+                let target = window_ref.normalize(primary_window);
+                camera.is_active && target.map(|target| target.entity()) == Some(window)
+            })
+            .max_by_key(|(camera, _)| camera.order)
+            .and_then(|(camera, transform)| camera.viewport_to_world(transform, viewport_position));
+
+        ViewportCursor {
+            viewport_position,
+            ray,
+        }
+    }
+
+    pub fn point_on_plane(&self, origin: Vec3, normal: Dir3) -> Option<Vec3> {
+        let ray = self.ray?;
+        let distance = ray.intersect_plane(origin, InfinitePlane3d::new(normal))?;
+        Some(ray.get_point(distance))
+    }
+
+    /// The cursor on the ground plane (`y = 0`), or on the `z = 0` plane for cameras looking down the z axis like 2d cameras
+    pub fn world_position(&self) -> Option<Vec3> {
+        let ray = self.ray?;
+        let normal = match ray.direction.dot(Vec3::Z).abs() > 0.999 {
+            true => Dir3::Z,
+            false => Dir3::Y,
+        };
+        self.point_on_plane(Vec3::ZERO, normal)
+    }
+}
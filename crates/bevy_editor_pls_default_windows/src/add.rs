@@ -7,10 +7,11 @@ use bevy::{
     render::view::RenderLayers,
 };
 use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::Selection;
 use bevy_inspector_egui::egui;
 use indexmap::IndexMap;
 
-use crate::hierarchy::HierarchyWindow;
+use crate::cameras::{camera_3d_panorbit::PanOrbitCamera, ActiveEditorCamera};
 
 pub struct AddItem {
     name: Cow<'static, str>,
@@ -54,6 +55,8 @@ impl AddItem {
 
 pub struct AddWindowState {
     sections: IndexMap<&'static str, Vec<AddItem>>,
+    /// Parent new entities under the first selected entity, instead of spawning them at the root
+    pub spawn_as_child: bool,
 }
 
 impl AddWindowState {
@@ -74,6 +77,7 @@ impl EditorWindow for AddWindow {
     type State = AddWindowState;
 
     const NAME: &'static str = "Add";
+    const CATEGORY: &'static str = "Scene";
 
     fn ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
         add_ui_button(world, ui, cx);
@@ -82,23 +86,55 @@ impl EditorWindow for AddWindow {
     fn menu_ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
         add_ui_button(world, ui, cx);
     }
+
+    fn save_state(state: &Self::State) -> Option<String> {
+        ron::to_string(&state.spawn_as_child).ok()
+    }
+
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Ok(spawn_as_child) = ron::from_str(saved) {
+            state.spawn_as_child = spawn_as_child;
+        }
+    }
 }
 
 fn add_ui_button(world: &mut World, ui: &mut egui::Ui, mut cx: EditorWindowContext) {
-    let state = cx.state::<AddWindow>().unwrap();
+    let state = cx.state_mut::<AddWindow>().unwrap();
 
     let response = ui.menu_button("Add", |ui| {
+        ui.checkbox(&mut state.spawn_as_child, "Spawn as child of selection");
+        ui.separator();
+
         add_ui(ui, state).map(|add_item| {
+            let parent = match state.spawn_as_child {
+                true => world.resource::<Selection>().selected.first().copied(),
+                false => None,
+            };
+
             let entity = world.spawn_empty().id();
             add_item.add_to_entity(world, entity);
+            if let Some(parent) = parent.filter(|&parent| world.get_entity(parent).is_some()) {
+                set_parent_keep_placement(world, entity, parent);
+            }
             entity
         })
     });
 
     if let Some(Some(entity)) = response.inner {
-        if let Some(hierarchy_state) = cx.state_mut::<HierarchyWindow>() {
-            hierarchy_state.selected.select_replace(entity);
-        }
+        world.resource_mut::<Selection>().select(entity);
+    }
+}
+
+/// Parents the entity, adjusting its transform so it stays where it was placed in world space
+fn set_parent_keep_placement(world: &mut World, entity: Entity, parent: Entity) {
+    let parent_transform = world.get::<GlobalTransform>(parent).copied();
+
+    let mut entity = world.entity_mut(entity);
+    entity.set_parent(parent);
+    if let (Some(parent_transform), Some(mut transform)) =
+        (parent_transform, entity.get_mut::<Transform>())
+    {
+        *transform = GlobalTransform::from(*transform).reparented_to(&parent_transform);
     }
 }
 
@@ -129,10 +165,52 @@ pub fn add_ui<'a>(ui: &mut egui::Ui, state: &'a AddWindowState) -> Option<&'a Ad
     None
 }
 
+/// How far in front of editor cameras without a focus point new objects are placed
+const SPAWN_DISTANCE: f32 = 5.0;
+
+/// Where newly added objects are placed: the pivot of the pan/orbit camera,
+/// or in front of the other editor cameras.
+pub fn editor_camera_focus(world: &mut World) -> Vec3 {
+    let mut cameras = world
+        .query_filtered::<(&GlobalTransform, Option<&PanOrbitCamera>), With<ActiveEditorCamera>>();
+    match cameras.get_single(world) {
+        Ok((_, Some(pan_orbit))) => pan_orbit.focus,
+        Ok((transform, None)) => transform.translation() + transform.forward() * SPAWN_DISTANCE,
+        Err(_) => Vec3::ZERO,
+    }
+}
+
+fn add_primitive(world: &mut World, entity: Entity, name: &'static str, mesh: impl Into<Mesh>) {
+    let transform = Transform::from_translation(editor_camera_focus(world));
+
+    let mesh = world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let material = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial::default());
+
+    world.entity_mut(entity).insert((
+        Name::new(name),
+        PbrBundle {
+            mesh,
+            material,
+            transform,
+            ..default()
+        },
+    ));
+}
+
+/// How far above the camera focus new lights are placed
+const LIGHT_HEIGHT: f32 = 4.0;
+
+fn light_transform(world: &mut World) -> Transform {
+    Transform::from_translation(editor_camera_focus(world) + Vec3::Y * LIGHT_HEIGHT)
+}
+
 impl Default for AddWindowState {
     fn default() -> Self {
         let mut state = AddWindowState {
             sections: IndexMap::default(),
+            spawn_as_child: false,
         };
 
         state.add("", AddItem::bundle_named::<()>("Empty".into()));
@@ -193,25 +271,90 @@ impl Default for AddWindowState {
                 });
             }),
         );
-        state.add("3D", AddItem::bundle::<PointLightBundle>());
-        state.add("3D", AddItem::bundle::<DirectionalLightBundle>());
         state.add("3D", AddItem::bundle_named::<PbrBundle>("PbrBundle".into()));
+
         state.add(
-            "3D",
+            "Primitives",
             AddItem::new("Cube".into(), |world, entity| {
-                let mut meshes = world.get_resource_mut::<Assets<Mesh>>().unwrap();
-                let mesh = meshes.add(Cuboid::default());
-
-                let mut materials = world
-                    .get_resource_mut::<Assets<StandardMaterial>>()
-                    .unwrap();
-                let material = materials.add(StandardMaterial::default());
-
-                world.entity_mut(entity).insert(PbrBundle {
-                    mesh,
-                    material,
-                    ..Default::default()
-                });
+                add_primitive(world, entity, "Cube", Cuboid::default());
+            }),
+        );
+        state.add(
+            "Primitives",
+            AddItem::new("Sphere".into(), |world, entity| {
+                add_primitive(world, entity, "Sphere", Sphere::default());
+            }),
+        );
+        state.add(
+            "Primitives",
+            AddItem::new("Plane".into(), |world, entity| {
+                add_primitive(world, entity, "Plane", Plane3d::default());
+            }),
+        );
+        state.add(
+            "Primitives",
+            AddItem::new("Capsule".into(), |world, entity| {
+                add_primitive(world, entity, "Capsule", Capsule3d::default());
+            }),
+        );
+        state.add(
+            "Primitives",
+            AddItem::new("Cylinder".into(), |world, entity| {
+                add_primitive(world, entity, "Cylinder", Cylinder::default());
+            }),
+        );
+
+        state.add(
+            "Lights",
+            AddItem::new("Point Light".into(), |world, entity| {
+                let transform = light_transform(world);
+                world.entity_mut(entity).insert((
+                    Name::new("Point Light"),
+                    PointLightBundle {
+                        point_light: PointLight {
+                            shadows_enabled: true,
+                            ..default()
+                        },
+                        transform,
+                        ..default()
+                    },
+                ));
+            }),
+        );
+        state.add(
+            "Lights",
+            AddItem::new("Directional Light".into(), |world, entity| {
+                let transform = light_transform(world);
+                world.entity_mut(entity).insert((
+                    Name::new("Directional Light"),
+                    DirectionalLightBundle {
+                        directional_light: DirectionalLight {
+                            shadows_enabled: true,
+                            ..default()
+                        },
+                        // slanted, so shading shows the shape of lit objects
+                        transform: transform.looking_to(Vec3::new(-1.0, -2.0, -1.0), Vec3::Y),
+                        ..default()
+                    },
+                ));
+            }),
+        );
+        state.add(
+            "Lights",
+            AddItem::new("Spot Light".into(), |world, entity| {
+                let transform = light_transform(world);
+                let focus = transform.translation - Vec3::Y * LIGHT_HEIGHT;
+                world.entity_mut(entity).insert((
+                    Name::new("Spot Light"),
+                    SpotLightBundle {
+                        spot_light: SpotLight {
+                            shadows_enabled: true,
+                            ..default()
+                        },
+                        transform: transform.looking_at(focus, Vec3::Z),
+                        ..default()
+                    },
+                ));
             }),
         );
 
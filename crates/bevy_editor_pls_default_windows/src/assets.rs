@@ -1,65 +1,564 @@
-use bevy::{
-    asset::ReflectAsset,
-    prelude::{AppTypeRegistry, World},
-    reflect::TypeRegistry,
-};
+use std::any::TypeId;
+use std::cmp::Reverse;
+use std::sync::Arc;
+
+use bevy::asset::{AssetPath, LoadState, ReflectAsset, UntypedAssetId, UntypedHandle};
+use bevy::prelude::*;
+use bevy::reflect::TypeRegistry;
+use bevy::render::render_resource::{TextureDimension, TextureSampleType, TextureViewDimension};
 use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
-use bevy_inspector_egui::egui;
+use bevy_editor_pls_core::{Editor, Selection};
+use bevy_inspector_egui::egui::{self, load::SizedTexture};
 
+use crate::add::editor_camera_focus;
+use crate::cameras::ActiveEditorCamera;
 use crate::inspector::{InspectorSelection, InspectorWindow};
+use crate::undo::record_spawned;
+
+/// Edge length of the image thumbnails next to their entry
+const THUMBNAIL_SIZE: f32 = 32.0;
+/// Larger images are scaled down to fit into the preview window
+const PREVIEW_MAX_SIZE: f32 = 512.0;
+/// Assets dropped into the viewport are placed on the ground plane if it is closer than this,
+/// and this far along the cursor ray otherwise
+const MAX_DROP_DISTANCE: f32 = 100.0;
+const DEFAULT_DROP_DISTANCE: f32 = 5.0;
+
+/// Payload of assets dragged out of the [`AssetsWindow`], spawning an entity using the asset
+/// when dropped into the viewport.
+#[derive(Debug, Clone)]
+pub struct AssetDragPayload {
+    pub type_name: String,
+    pub id: UntypedAssetId,
+}
+
+/// Only meshes and scenes can be spawned by dropping them into the viewport
+fn is_spawnable(id: UntypedAssetId) -> bool {
+    id.type_id() == TypeId::of::<Mesh>() || id.type_id() == TypeId::of::<Scene>()
+}
+
+/// An asset dropped into the viewport, spawned once the editor UI is done
+struct DroppedAsset {
+    id: UntypedAssetId,
+    /// Where it was dropped, in egui points
+    pos: egui::Pos2,
+}
+
+/// Order of the assets of each type in the [`AssetsWindow`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AssetSort {
+    /// The order of [`Assets::ids`]
+    #[default]
+    Unsorted,
+    /// Most strong handles first, to find assets which are referenced more than expected
+    MostReferences,
+    /// Fewest strong handles first, to find assets nothing uses anymore
+    FewestReferences,
+}
+
+impl AssetSort {
+    pub const ALL: &'static [AssetSort] = &[
+        AssetSort::Unsorted,
+        AssetSort::MostReferences,
+        AssetSort::FewestReferences,
+    ];
+}
+
+impl std::fmt::Display for AssetSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetSort::Unsorted => write!(f, "Unsorted"),
+            AssetSort::MostReferences => write!(f, "Most references"),
+            AssetSort::FewestReferences => write!(f, "Fewest references"),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AssetsWindowState {
+    /// Only list assets whose type name, source path or id contains this, ignoring case
+    pub filter: String,
+    pub sort: AssetSort,
+    /// The image shown in the preview window, opened by clicking its thumbnail
+    pub preview: Option<AssetId<Image>>,
+    dropped: Option<DroppedAsset>,
+}
 
 pub struct AssetsWindow;
 
 impl EditorWindow for AssetsWindow {
-    type State = ();
+    type State = AssetsWindowState;
     const NAME: &'static str = "Assets";
+    const CATEGORY: &'static str = "Scene";
+    const ICON: Option<&'static str> = Some("🖼");
 
     fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
-        let selection = &mut cx.state_mut::<InspectorWindow>().unwrap().selected;
-        let type_registry = world.resource::<AppTypeRegistry>();
+        let type_registry = world.resource::<AppTypeRegistry>().0.clone();
         let type_registry = type_registry.read();
 
-        select_asset(ui, &type_registry, world, selection);
+        let state = cx.state_mut::<AssetsWindow>().unwrap();
+        ui.add(
+            egui::TextEdit::singleline(&mut state.filter)
+                .hint_text("Filter by type or path")
+                .desired_width(f32::INFINITY),
+        );
+        egui::ComboBox::from_label("Sort")
+            .selected_text(state.sort.to_string())
+            .show_ui(ui, |ui| {
+                for &sort in AssetSort::ALL {
+                    ui.selectable_value(&mut state.sort, sort, sort.to_string());
+                }
+            });
+        let filter = state.filter.trim().to_lowercase();
+        let sort = state.sort;
+        let mut preview = state.preview;
+
+        let selected = match cx.state::<InspectorWindow>().unwrap().selected {
+            InspectorSelection::Asset(_, _, selected) => Some(selected),
+            _ => None,
+        };
+
+        let mut list = AssetList {
+            world,
+            cx: &mut cx,
+            type_registry: &type_registry,
+            filter: &filter,
+            sort,
+            selected,
+            preview: &mut preview,
+        };
+        let new_selection = egui::ScrollArea::vertical()
+            .show(ui, |ui| list.show(ui))
+            .inner;
+
+        if preview.is_some() {
+            image_preview_ui(world, &mut cx, ui.ctx(), &mut preview);
+        }
+
+        cx.state_mut::<AssetsWindow>().unwrap().preview = preview;
+        if let Some(new_selection) = new_selection {
+            cx.state_mut::<InspectorWindow>().unwrap().selected = new_selection;
+        }
+    }
+
+    fn viewport_ui(_: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let Some(payload) = egui::DragAndDrop::payload::<AssetDragPayload>(ui.ctx()) else {
+            return;
+        };
+        let pointer_pos = ui.ctx().pointer_latest_pos();
+        let Some(pos) = pointer_pos.filter(|&pos| ui.clip_rect().contains(pos)) else {
+            return;
+        };
+
+        let spawnable = is_spawnable(payload.id);
+        let (hint, cursor) = if spawnable {
+            ("Drop to spawn".to_owned(), egui::CursorIcon::Grabbing)
+        } else {
+            let hint = format!("{} can't be spawned", payload.type_name);
+            (hint, egui::CursorIcon::NoDrop)
+        };
+        ui.ctx().set_cursor_icon(cursor);
+        ui.painter().text(
+            pos + egui::vec2(16.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            hint,
+            egui::TextStyle::Body.resolve(ui.style()),
+            ui.visuals().strong_text_color(),
+        );
+
+        if spawnable && ui.input(|input| input.pointer.any_released()) {
+            egui::DragAndDrop::clear_payload(ui.ctx());
+            let state = cx.state_mut::<AssetsWindow>().unwrap();
+            state.dropped = Some(DroppedAsset {
+                id: payload.id,
+                pos,
+            });
+        }
+    }
+
+    /// Reopening the window shouldn't bring back the preview, so its texture is released
+    fn on_close(_: &mut World, state: &mut Self::State) {
+        state.preview = None;
+    }
+
+    fn app_setup(app: &mut App) {
+        app.add_systems(Update, spawn_dropped_asset);
     }
 }
 
-fn select_asset(
-    ui: &mut egui::Ui,
-    type_registry: &TypeRegistry,
-    world: &World,
-    selection: &mut InspectorSelection,
-) {
-    let mut assets: Vec<_> = type_registry
-        .iter()
-        .filter_map(|registration| {
-            let reflect_asset = registration.data::<ReflectAsset>()?;
-            Some((
-                registration.type_info().type_path_table().short_path(),
-                registration.type_id(),
-                reflect_asset,
-            ))
-        })
-        .collect();
-    assets.sort_by(|(name_a, ..), (name_b, ..)| name_a.cmp(name_b));
-
-    for (asset_name, asset_type_id, reflect_asset) in assets {
-        let handles: Vec<_> = reflect_asset.ids(world).collect();
-
-        ui.collapsing(format!("{asset_name} ({})", handles.len()), |ui| {
-            for handle in handles {
-                let selected = match *selection {
-                    InspectorSelection::Asset(_, _, selected_id) => selected_id == handle,
-                    _ => false,
-                };
-
-                if ui
-                    .selectable_label(selected, format!("{:?}", handle))
-                    .clicked()
-                {
-                    *selection =
-                        InspectorSelection::Asset(asset_type_id, asset_name.to_owned(), handle);
+/// Spawns the asset dropped into the viewport where the cursor ray of the editor camera
+/// hits the ground, and selects it
+fn spawn_dropped_asset(world: &mut World) {
+    let mut editor = world.resource_mut::<Editor>();
+    let Some(state) = editor.window_state_mut::<AssetsWindow>() else {
+        return;
+    };
+    let Some(dropped) = state.dropped.take() else {
+        return;
+    };
+
+    let mut cameras =
+        world.query_filtered::<(&Camera, &GlobalTransform), With<ActiveEditorCamera>>();
+    let editor = world.resource::<Editor>();
+    let ray = cameras
+        .get_single(world)
+        .ok()
+        .and_then(|(camera, transform)| editor.viewport_ray(dropped.pos, camera, transform));
+    let translation = match ray {
+        Some(ray) => {
+            let distance = ray
+                .intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+                .filter(|&distance| distance < MAX_DROP_DISTANCE)
+                .unwrap_or(DEFAULT_DROP_DISTANCE);
+            ray.get_point(distance)
+        }
+        None => editor_camera_focus(world),
+    };
+    let transform = Transform::from_translation(translation);
+
+    let asset_server = world.get_resource::<AssetServer>();
+    let file_name = asset_path(asset_server, dropped.id).and_then(|path| {
+        let stem = path.path().file_stem()?;
+        Some(stem.to_string_lossy().into_owned())
+    });
+
+    let entity = if dropped.id.type_id() == TypeId::of::<Mesh>() {
+        let Some(mesh) = world
+            .resource_mut::<Assets<Mesh>>()
+            .get_strong_handle(dropped.id.typed())
+        else {
+            return;
+        };
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let name = Name::new(file_name.unwrap_or_else(|| "Mesh".to_owned()));
+        let bundle = PbrBundle {
+            mesh,
+            material,
+            transform,
+            ..default()
+        };
+        world.spawn((name, bundle)).id()
+    } else {
+        let Some(scene) = world
+            .resource_mut::<Assets<Scene>>()
+            .get_strong_handle(dropped.id.typed())
+        else {
+            return;
+        };
+        let name = Name::new(file_name.unwrap_or_else(|| "Scene".to_owned()));
+        let bundle = SceneBundle {
+            scene,
+            transform,
+            ..default()
+        };
+        world.spawn((name, bundle)).id()
+    };
+
+    record_spawned(world, &[entity], "Drop asset");
+    world.resource_mut::<Selection>().select(entity);
+    let mut editor = world.resource_mut::<Editor>();
+    if let Some(inspector_state) = editor.window_state_mut::<InspectorWindow>() {
+        inspector_state.selected = InspectorSelection::Entities;
+    }
+}
+
+/// The source path of assets loaded through the [`AssetServer`], the id otherwise
+fn asset_label(asset_server: Option<&AssetServer>, id: UntypedAssetId) -> String {
+    asset_path(asset_server, id).map_or_else(|| format!("{id:?}"), |path| path.to_string())
+}
+
+/// `None` for assets which were added at runtime instead of being loaded
+fn asset_path(
+    asset_server: Option<&AssetServer>,
+    id: UntypedAssetId,
+) -> Option<AssetPath<'static>> {
+    asset_server
+        .and_then(|asset_server| asset_server.get_path(id))
+        .map(AssetPath::into_owned)
+}
+
+struct AssetList<'a, 'cx> {
+    world: &'a mut World,
+    cx: &'a mut EditorWindowContext<'cx>,
+    type_registry: &'a TypeRegistry,
+    filter: &'a str,
+    sort: AssetSort,
+    selected: Option<UntypedAssetId>,
+    preview: &'a mut Option<AssetId<Image>>,
+}
+
+impl AssetList<'_, '_> {
+    /// Lists the assets of each type, returning the newly clicked asset
+    fn show(&mut self, ui: &mut egui::Ui) -> Option<InspectorSelection> {
+        let mut assets: Vec<_> = self
+            .type_registry
+            .iter()
+            .filter_map(|registration| {
+                let reflect_asset = registration.data::<ReflectAsset>()?;
+                Some((
+                    registration.type_info().type_path_table().short_path(),
+                    registration.type_id(),
+                    reflect_asset,
+                ))
+            })
+            .collect();
+        assets.sort_by_key(|(name, ..)| *name);
+
+        let filter = self.filter;
+        let mut new_selection = None;
+        let mut any_shown = false;
+        let asset_server = self.world.get_resource::<AssetServer>().cloned();
+        for (asset_name, asset_type_id, reflect_asset) in assets {
+            let type_matches = asset_name.to_lowercase().contains(filter);
+            let mut handles: Vec<_> = reflect_asset
+                .ids(self.world)
+                .map(|handle| (handle, asset_label(asset_server.as_ref(), handle)))
+                .filter(|(handle, label)| {
+                    type_matches
+                        || label.to_lowercase().contains(filter)
+                        || format!("{handle:?}").to_lowercase().contains(filter)
+                })
+                .map(|(handle, label)| {
+                    let references = asset_server
+                        .as_ref()
+                        .and_then(|asset_server| reference_count(asset_server, handle));
+                    (handle, label, references)
+                })
+                .collect();
+            if !filter.is_empty() && handles.is_empty() {
+                continue;
+            }
+            any_shown = true;
+
+            // assets with unknown counts go last either way
+            match self.sort {
+                AssetSort::Unsorted => {}
+                AssetSort::MostReferences => {
+                    handles.sort_by_key(|&(_, _, references)| Reverse(references));
                 }
+                AssetSort::FewestReferences => {
+                    handles.sort_by_key(|&(_, _, references)| (references.is_none(), references));
+                }
+            }
+
+            let is_image = asset_type_id == TypeId::of::<Image>();
+            ui.collapsing(format!("{asset_name} ({})", handles.len()), |ui| {
+                for (handle, label, references) in handles {
+                    let clicked = ui
+                        .horizontal(|ui| {
+                            if is_image {
+                                self.thumbnail_ui(ui, handle.typed::<Image>());
+                            }
+                            let selected = self.selected == Some(handle);
+                            let response = ui
+                                .selectable_label(selected, label)
+                                .interact(egui::Sense::drag());
+                            response.dnd_set_drag_payload(AssetDragPayload {
+                                type_name: asset_name.to_owned(),
+                                id: handle,
+                            });
+                            let clicked = response.clicked();
+                            if let Some(references) = references {
+                                ui.label(egui::RichText::new(references.to_string()).weak())
+                                    .on_hover_text("Strong handles to this asset");
+                            }
+                            if let Some(asset_server) = &asset_server {
+                                reload_ui(ui, asset_server, handle);
+                            }
+                            clicked
+                        })
+                        .inner;
+
+                    if clicked {
+                        new_selection = Some(InspectorSelection::Asset(
+                            asset_type_id,
+                            asset_name.to_owned(),
+                            handle,
+                        ));
+                    }
+                }
+            });
+        }
+
+        if !any_shown && !filter.is_empty() {
+            ui.label("No matching assets");
+        }
+
+        new_selection
+    }
+
+    /// Only registers the image with egui once the thumbnail is scrolled into view
+    fn thumbnail_ui(&mut self, ui: &mut egui::Ui, image: AssetId<Image>) {
+        let size = egui::Vec2::splat(THUMBNAIL_SIZE);
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+        if !ui.is_rect_visible(rect) {
+            return;
+        }
+
+        let undisplayable = self
+            .world
+            .get_resource::<Assets<Image>>()
+            .and_then(|images| images.get(image))
+            .and_then(undisplayable_reason);
+        if let Some(reason) = undisplayable {
+            let painter = ui.painter();
+            painter.rect_filled(rect, 2.0, ui.visuals().faint_bg_color);
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "?",
+                egui::FontId::proportional(THUMBNAIL_SIZE / 2.0),
+                ui.visuals().weak_text_color(),
+            );
+            response.on_hover_text(reason);
+            return;
+        }
+
+        let texture_id = self.cx.image_texture(self.world, &Handle::Weak(image));
+        egui::Image::new(SizedTexture::new(texture_id, size)).paint_at(ui, rect);
+
+        if response.on_hover_text("Click to preview").clicked() {
+            *self.preview = match *self.preview {
+                Some(previewed) if previewed == image => None,
+                _ => Some(image),
+            };
+        }
+    }
+}
+
+/// The number of strong [`Handle`]s to the asset.
+/// Only known for assets loaded through the [`AssetServer`], which tracks their handles.
+fn reference_count(asset_server: &AssetServer, id: UntypedAssetId) -> Option<usize> {
+    match asset_server.get_id_handle_untyped(id)? {
+        // without the handle which was just upgraded to count them
+        UntypedHandle::Strong(handle) => Some(Arc::strong_count(&handle) - 1),
+        UntypedHandle::Weak(_) => None,
+    }
+}
+
+/// A button reloading the asset from its path, and the error if loading it failed
+fn reload_ui(ui: &mut egui::Ui, asset_server: &AssetServer, id: UntypedAssetId) {
+    let path = asset_path(Some(asset_server), id);
+    let reload = ui
+        .add_enabled(path.is_some(), egui::Button::new("⟳").small())
+        .on_hover_text("Reload from disk")
+        .on_disabled_hover_text("Added at runtime, there is no file to reload it from");
+    if let Some(path) = path.filter(|_| reload.clicked()) {
+        asset_server.reload(path);
+    }
+
+    if let Some(LoadState::Failed(error)) = asset_server.get_load_state(id) {
+        ui.colored_label(egui::Color32::RED, "Failed")
+            .on_hover_text(error.to_string());
+    }
+}
+
+/// Why egui can't show the image, as it samples images as plain 2D textures with filtering
+fn undisplayable_reason(image: &Image) -> Option<String> {
+    let descriptor = &image.texture_descriptor;
+    let view_dimension = image
+        .texture_view_descriptor
+        .as_ref()
+        .and_then(|view| view.dimension);
+    if descriptor.dimension != TextureDimension::D2
+        || descriptor.size.depth_or_array_layers > 1
+        || view_dimension.is_some_and(|dimension| dimension != TextureViewDimension::D2)
+    {
+        return Some("Can't preview cubemaps, array or 3D textures".to_owned());
+    }
+    match descriptor.format.sample_type(None, None) {
+        Some(TextureSampleType::Float { filterable: true }) => None,
+        _ => Some(format!(
+            "Can't preview {:?} images, they can't be sampled with filtering",
+            descriptor.format
+        )),
+    }
+}
+
+fn image_preview_ui(
+    world: &mut World,
+    cx: &mut EditorWindowContext,
+    ctx: &egui::Context,
+    preview: &mut Option<AssetId<Image>>,
+) {
+    let Some(image) = *preview else {
+        return;
+    };
+    let (image_size, undisplayable) = world
+        .get_resource::<Assets<Image>>()
+        .and_then(|images| images.get(image))
+        .map(|image| (Some(image.size_f32()), undisplayable_reason(image)))
+        .unwrap_or_default();
+    let title = asset_label(world.get_resource::<AssetServer>(), image.untyped());
+
+    let mut open = true;
+    egui::Window::new("Image Preview")
+        .id(egui::Id::new("assets_image_preview"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(title);
+            let Some(image_size) = image_size else {
+                ui.label("Not loaded");
+                return;
+            };
+            if let Some(reason) = undisplayable {
+                ui.label(reason);
+                return;
             }
+
+            let size = egui::vec2(image_size.x, image_size.y);
+            let size = size * (PREVIEW_MAX_SIZE / size.max_elem()).min(1.0);
+            let texture_id = cx.image_texture(world, &Handle::Weak(image));
+            ui.image(SizedTexture::new(texture_id, size));
+            ui.label(format!("{} × {}", image_size.x, image_size.y));
         });
+
+    if !open {
+        *preview = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::render_asset::RenderAssetUsages;
+    use bevy::render::render_resource::{Extent3d, TextureFormat, TextureViewDescriptor};
+
+    use super::*;
+
+    fn image(layers: u32, format: TextureFormat) -> Image {
+        let size = Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: layers,
+        };
+        let pixel = vec![0; format.block_copy_size(None).unwrap() as usize];
+        Image::new_fill(
+            size,
+            TextureDimension::D2,
+            &pixel,
+            format,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn only_filterable_2d_images_are_displayable() {
+        assert_eq!(
+            undisplayable_reason(&image(1, TextureFormat::Rgba8UnormSrgb)),
+            None
+        );
+
+        let mut cubemap = image(6, TextureFormat::Rgba8UnormSrgb);
+        cubemap.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..default()
+        });
+        assert!(undisplayable_reason(&cubemap).is_some());
+
+        assert!(undisplayable_reason(&image(1, TextureFormat::R32Float)).is_some());
+        assert!(undisplayable_reason(&image(1, TextureFormat::Depth32Float)).is_some());
     }
 }
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use bevy_editor_pls_core::Editor;
+use bevy_inspector_egui::egui;
+
+use super::{
+    camera_3d_panorbit::PanOrbitCamera, move_active_camera, ActiveEditorCamera, CameraWindow,
+    CameraWindowState, EditorCamKind,
+};
+
+pub const BOOKMARK_SLOTS: usize = 9;
+
+/// Keys restoring the bookmark slots, saving them while `Ctrl` is held
+const SLOT_KEYS: [KeyCode; BOOKMARK_SLOTS] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// A saved editor camera viewpoint, unlike a [`SceneView`](super::views::SceneView)
+/// without the selection and visibility
+#[derive(Clone, Copy)]
+pub struct CameraBookmark {
+    pub camera: EditorCamKind,
+    pub transform: Transform,
+    /// Focus and radius of the pan/orbit camera
+    pub pan_orbit: Option<(Vec3, f32)>,
+    pub orthographic_scale: Option<f32>,
+}
+
+pub(super) fn bookmarks_ui(world: &mut World, state: &mut CameraWindowState, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.label("Bookmarks");
+        ui.checkbox(&mut state.smooth_bookmarks, "Smooth")
+            .on_hover_text("Animate the camera to restored bookmarks");
+    });
+
+    ui.horizontal(|ui| {
+        for slot in 0..BOOKMARK_SLOTS {
+            let bookmark = state.bookmarks[slot];
+            let label = egui::RichText::new((slot + 1).to_string());
+            let button = match bookmark {
+                Some(_) => ui.button(label.strong()).on_hover_text(format!(
+                    "Restore (key {0}), right click to overwrite (Ctrl+{0})",
+                    slot + 1
+                )),
+                None => ui
+                    .button(label.weak())
+                    .on_hover_text(format!("Save the camera (Ctrl+{})", slot + 1)),
+            };
+
+            match bookmark {
+                Some(bookmark) if button.clicked() => restore_bookmark(world, state, bookmark),
+                _ if button.clicked() || button.secondary_clicked() => {
+                    state.bookmarks[slot] = capture_bookmark(world, state.editor_cam);
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+pub fn capture_bookmark(world: &mut World, camera: EditorCamKind) -> Option<CameraBookmark> {
+    let mut active_camera = world.query_filtered::<(
+        &Transform,
+        Option<&PanOrbitCamera>,
+        Option<&OrthographicProjection>,
+    ), With<ActiveEditorCamera>>();
+    let (&transform, pan_orbit, orthographic) = active_camera.get_single(world).ok()?;
+
+    Some(CameraBookmark {
+        camera,
+        transform,
+        pan_orbit: pan_orbit.map(|pan_orbit| (pan_orbit.focus, pan_orbit.radius)),
+        orthographic_scale: orthographic.map(|orthographic| orthographic.scale),
+    })
+}
+
+pub fn restore_bookmark(
+    world: &mut World,
+    state: &mut CameraWindowState,
+    bookmark: CameraBookmark,
+) {
+    CameraWindow::set_active_camera(world, state, bookmark.camera);
+    let duration = match state.smooth_bookmarks {
+        true => state.transition_duration,
+        false => 0.0,
+    };
+    move_active_camera(
+        world,
+        bookmark.transform,
+        bookmark.pan_orbit,
+        bookmark.orthographic_scale,
+        duration,
+    );
+}
+
+pub(super) fn bookmark_hotkeys(world: &mut World) {
+    let Some(keys) = world.get_resource::<ButtonInput<KeyCode>>() else {
+        return;
+    };
+    let Some(slot) = SLOT_KEYS.iter().position(|&key| keys.just_pressed(key)) else {
+        return;
+    };
+    let save = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    world.resource_scope(|world, mut editor: Mut<Editor>| {
+        if !editor.active() || editor.listening_for_text() || editor.passive_input() {
+            return;
+        }
+        let Some(state) = editor.window_state_mut::<CameraWindow>() else {
+            return;
+        };
+
+        if save {
+            state.bookmarks[slot] = capture_bookmark(world, state.editor_cam);
+        } else if let Some(bookmark) = state.bookmarks[slot] {
+            restore_bookmark(world, state, bookmark);
+        }
+    });
+}
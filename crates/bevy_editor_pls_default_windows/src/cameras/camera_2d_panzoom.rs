@@ -42,8 +42,12 @@ fn camera_zoom(
         return;
     }
 
-    for (_cam, mut projection) in query.iter_mut() {
-        projection.scale = (projection.scale * (1. + -scroll * 0.001)).max(0.00001);
+    for (cam, mut projection) in query.iter_mut() {
+        if !cam.enabled {
+            continue;
+        }
+        let zoom = 1. + -scroll * 0.001 * cam.zoom_speed;
+        projection.scale = (projection.scale * zoom).max(0.00001);
     }
 }
 
@@ -82,7 +86,9 @@ fn camera_movement(
                 window.height() / projection.area.height(),
             ) * projection.scale;
 
-            transform.translation -= (delta * scaling).extend(0.);
+            // along the camera's own axes, for the orthographic views of the 3d scene
+            let rotation = transform.rotation;
+            transform.translation -= rotation * (delta * scaling).extend(0.);
         }
     }
     *last_pos = Some(current_pos);
@@ -92,6 +98,8 @@ fn camera_movement(
 pub struct PanCamControls {
     pub enabled: bool,
     pub grab_buttons: Vec<MouseButton>,
+    /// Multiplier of the scroll zoom
+    pub zoom_speed: f32,
 }
 
 impl Default for PanCamControls {
@@ -99,6 +107,7 @@ impl Default for PanCamControls {
         Self {
             enabled: true,
             grab_buttons: vec![MouseButton::Left, MouseButton::Right, MouseButton::Middle],
+            zoom_speed: 1.0,
         }
     }
 }
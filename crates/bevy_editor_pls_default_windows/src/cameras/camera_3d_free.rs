@@ -16,11 +16,15 @@ pub(crate) enum CameraSystem {
     EditorCam3dFree,
 }
 
+pub const BOOST_MULTIPLIER: f32 = 4.0;
+
 #[derive(Component)]
 pub struct FlycamControls {
     pub yaw: f32,
     pub pitch: f32,
     pub sensitivity: f32,
+    /// Units per second, multiplied by [`BOOST_MULTIPLIER`] while holding `key_boost`
+    pub speed: f32,
     pub enable_movement: bool,
     pub enable_look: bool,
 
@@ -38,6 +42,7 @@ impl Default for FlycamControls {
             yaw: Default::default(),
             pitch: Default::default(),
             sensitivity: 1.0,
+            speed: 5.0,
             enable_movement: false,
             enable_look: false,
             key_forward: KeyCode::KeyW,
@@ -53,7 +58,8 @@ impl Default for FlycamControls {
 
 fn camera_movement(
     mut cam: Query<(&FlycamControls, &mut Transform)>,
-    time: Res<Time>,
+    // keeps moving while the game is paused
+    time: Res<Time<Real>>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
 ) {
     let (flycam, mut cam_transform) = cam.single_mut();
@@ -74,9 +80,9 @@ fn camera_movement(
     }
 
     let speed = if keyboard_input.pressed(flycam.key_boost) {
-        20.0
+        flycam.speed * BOOST_MULTIPLIER
     } else {
-        5.0
+        flycam.speed
     };
 
     let movement =
@@ -25,6 +25,10 @@ pub struct PanOrbitCamera {
     pub focus: Vec3,
     pub radius: f32,
     pub upside_down: bool,
+    /// Multipliers of the mouse input
+    pub pan_speed: f32,
+    pub orbit_speed: f32,
+    pub zoom_speed: f32,
 
     pub orbit_button: MouseButton,
     pub pan_button: MouseButton,
@@ -38,6 +42,9 @@ impl Default for PanOrbitCamera {
             focus: Vec3::ZERO,
             radius: 5.0,
             upside_down: false,
+            pan_speed: 1.0,
+            orbit_speed: 1.0,
+            zoom_speed: 1.0,
 
             orbit_button: MouseButton::Right,
             pan_button: MouseButton::Middle,
@@ -115,14 +122,14 @@ fn pan_orbit_camera(
     if rotation_move.length_squared() > 0.0 {
         any = true;
         let delta_x = {
-            let delta = rotation_move.x / 180.0;
+            let delta = rotation_move.x / 180.0 * pan_orbit.orbit_speed;
             if pan_orbit.upside_down {
                 -delta
             } else {
                 delta
             }
         };
-        let delta_y = rotation_move.y / 180.0;
+        let delta_y = rotation_move.y / 180.0 * pan_orbit.orbit_speed;
         let yaw = Quat::from_rotation_y(-delta_x);
         let pitch = Quat::from_rotation_x(-delta_y);
         transform.rotation = yaw * transform.rotation; // rotate around global y axis
@@ -139,11 +146,11 @@ fn pan_orbit_camera(
         let right = transform.rotation * Vec3::X * -pan.x;
         let up = transform.rotation * Vec3::Y * pan.y;
         // make panning proportional to distance away from focus point
-        let translation = (right + up) * pan_orbit.radius;
+        let translation = (right + up) * pan_orbit.radius * pan_orbit.pan_speed;
         pan_orbit.focus += translation;
     } else if scroll.abs() > 0.0 {
         any = true;
-        pan_orbit.radius -= scroll * pan_orbit.radius * 0.1;
+        pan_orbit.radius -= scroll * pan_orbit.radius * 0.1 * pan_orbit.zoom_speed;
         // dont allow zoom to reach zero or you get stuck
         pan_orbit.radius = f32::max(pan_orbit.radius, 0.05);
     }
@@ -1,8 +1,13 @@
+pub mod bookmarks;
 pub mod camera_2d_panzoom;
 pub mod camera_3d_free;
 pub mod camera_3d_panorbit;
+mod navigation;
+mod screenshot;
+pub mod views;
 use crate::scenes::NotInScene;
 
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::render::camera::RenderTarget;
 use bevy::render::view::RenderLayers;
 use bevy::utils::HashSet;
@@ -10,13 +15,15 @@ use bevy::window::WindowRef;
 use bevy::{prelude::*, render::primitives::Aabb};
 use bevy_editor_pls_core::{
     editor_window::{EditorWindow, EditorWindowContext},
-    Editor, EditorEvent,
+    Editor, EditorEvent, Selection,
 };
 use bevy_inspector_egui::egui;
-use transform_gizmo_bevy::GizmoCamera;
+use serde::{Deserialize, Serialize};
+use transform_gizmo_bevy::{GizmoCamera, GizmoTarget};
 // use bevy_mod_picking::prelude::PickRaycastSource;
 
-use crate::hierarchy::{HideInEditor, HierarchyWindow};
+use crate::debug_settings::DebugSettingsWindow;
+use crate::hierarchy::HideInEditor;
 
 use self::camera_3d_panorbit::PanOrbitCamera;
 
@@ -42,14 +49,85 @@ struct EditorCamera3dPanOrbit;
 #[derive(Component)]
 struct EditorCamera2dPanZoom;
 
+// Marker component for the 3d orthographic camera used by the top/front/side views
+#[derive(Component)]
+struct EditorCamera3dOrthographic;
+
+/// How far from the focus point the orthographic views place the camera
+const ORTHOGRAPHIC_DISTANCE: f32 = 500.0;
+
 pub struct CameraWindow;
 
+impl CameraWindow {
+    /// Makes `camera` the active editor camera.
+    /// The orthographic views are aligned to look at the focus point of the previous camera.
+    pub fn set_active_camera(
+        world: &mut World,
+        state: &mut CameraWindowState,
+        camera: EditorCamKind,
+    ) {
+        if state.editor_cam == camera {
+            return;
+        }
+
+        // an interrupted transition continues from where it was, leaving its camera at the target
+        let interrupted = world.resource_mut::<CameraTransition>().0.take();
+        let mut active_camera = world.query_filtered::<&Transform, With<ActiveEditorCamera>>();
+        let outgoing = active_camera.get_single(world).ok().copied();
+        if let Some(interrupted) = interrupted {
+            if let Some(mut transform) = world.get_mut::<Transform>(interrupted.camera) {
+                *transform = interrupted.to;
+            }
+        }
+
+        let focus = crate::add::editor_camera_focus(world);
+        let previous = std::mem::replace(&mut state.editor_cam, camera);
+        set_active_editor_camera_marker(world, camera);
+
+        if let Some((direction, up)) = camera.orthographic_view() {
+            let mut orthographic =
+                world.query_filtered::<&mut Transform, With<EditorCamera3dOrthographic>>();
+            if let Ok(mut transform) = orthographic.get_single_mut(world) {
+                *transform = Transform::from_translation(focus + direction * ORTHOGRAPHIC_DISTANCE)
+                    .looking_at(focus, up);
+            }
+        }
+
+        // the 2d camera only makes sense without rotation, so it isn't animated
+        let is_2d = |kind: EditorCamKind| kind == EditorCamKind::D2PanZoom;
+        if state.transition_duration <= 0.0 || is_2d(previous) || is_2d(camera) {
+            return;
+        }
+        let Some(outgoing) = outgoing else {
+            return;
+        };
+        let mut active_camera =
+            world.query_filtered::<(Entity, &mut Transform), With<ActiveEditorCamera>>();
+        let Ok((entity, mut transform)) = active_camera.get_single_mut(world) else {
+            return;
+        };
+        let to = std::mem::replace(&mut *transform, outgoing);
+        world.resource_mut::<CameraTransition>().0 = Some(CameraTransitionState {
+            camera: entity,
+            from: outgoing,
+            to,
+            elapsed: 0.0,
+            duration: state.transition_duration,
+        });
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Default)]
 pub enum EditorCamKind {
     D2PanZoom,
     D3Free,
     #[default]
     D3PanOrbit,
+    D3OrthoTop,
+    D3OrthoFront,
+    D3OrthoBack,
+    D3OrthoLeft,
+    D3OrthoRight,
 }
 
 impl EditorCamKind {
@@ -58,23 +136,96 @@ impl EditorCamKind {
             EditorCamKind::D2PanZoom => "2D (Pan/Zoom)",
             EditorCamKind::D3Free => "3D (Free)",
             EditorCamKind::D3PanOrbit => "3D (Pan/Orbit)",
+            EditorCamKind::D3OrthoTop => "Top (Orthographic)",
+            EditorCamKind::D3OrthoFront => "Front (Orthographic)",
+            EditorCamKind::D3OrthoBack => "Back (Orthographic)",
+            EditorCamKind::D3OrthoLeft => "Left (Orthographic)",
+            EditorCamKind::D3OrthoRight => "Right (Orthographic)",
         }
     }
 
-    fn all() -> [EditorCamKind; 3] {
+    fn all() -> [EditorCamKind; 8] {
         [
             EditorCamKind::D2PanZoom,
             EditorCamKind::D3Free,
             EditorCamKind::D3PanOrbit,
+            EditorCamKind::D3OrthoTop,
+            EditorCamKind::D3OrthoFront,
+            EditorCamKind::D3OrthoBack,
+            EditorCamKind::D3OrthoLeft,
+            EditorCamKind::D3OrthoRight,
         ]
     }
+
+    /// Direction from the focus point to the camera and the camera's up direction
+    /// for the orthographic views
+    fn orthographic_view(self) -> Option<(Vec3, Vec3)> {
+        match self {
+            EditorCamKind::D3OrthoTop => Some((Vec3::Y, Vec3::NEG_Z)),
+            EditorCamKind::D3OrthoFront => Some((Vec3::Z, Vec3::Y)),
+            EditorCamKind::D3OrthoBack => Some((Vec3::NEG_Z, Vec3::Y)),
+            EditorCamKind::D3OrthoLeft => Some((Vec3::NEG_X, Vec3::Y)),
+            EditorCamKind::D3OrthoRight => Some((Vec3::X, Vec3::Y)),
+            EditorCamKind::D2PanZoom | EditorCamKind::D3Free | EditorCamKind::D3PanOrbit => None,
+        }
+    }
+}
+
+/// Speeds of the editor camera controllers, applied to their components every frame
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct CameraSpeeds {
+    /// Units per second of the free camera, multiplied while holding the boost key
+    pub fly: f32,
+    /// Multipliers of the pan/orbit camera's mouse input
+    pub pan: f32,
+    pub orbit: f32,
+    /// Multiplier of the scroll zoom of the pan/orbit and orthographic cameras
+    pub zoom: f32,
+}
+
+impl Default for CameraSpeeds {
+    fn default() -> Self {
+        CameraSpeeds {
+            fly: 5.0,
+            pan: 1.0,
+            orbit: 1.0,
+            zoom: 1.0,
+        }
+    }
 }
 
-#[derive(Default)]
 pub struct CameraWindowState {
     // make sure to keep the `ActiveEditorCamera` marker component in sync with this field
     editor_cam: EditorCamKind,
     pub show_ui: bool,
+    /// Saved camera, selection and visibility combinations
+    pub views: Vec<views::SceneView>,
+    new_view_name: String,
+    /// Seconds the camera takes to move over from the previous one when switching, 0 to snap
+    pub transition_duration: f32,
+    /// Camera viewpoints saved for the session, restored by clicking them or with the number keys
+    pub bookmarks: [Option<bookmarks::CameraBookmark>; bookmarks::BOOKMARK_SLOTS],
+    /// Animate the camera to restored bookmarks
+    pub smooth_bookmarks: bool,
+    pub speeds: CameraSpeeds,
+    /// Entity kept centered by the editor camera, until it is deselected or `Escape` is pressed
+    pub follow: Option<Entity>,
+}
+
+impl Default for CameraWindowState {
+    fn default() -> Self {
+        CameraWindowState {
+            editor_cam: EditorCamKind::default(),
+            show_ui: false,
+            views: Vec::new(),
+            new_view_name: String::new(),
+            transition_duration: 0.25,
+            bookmarks: [None; bookmarks::BOOKMARK_SLOTS],
+            smooth_bookmarks: true,
+            speeds: CameraSpeeds::default(),
+            follow: None,
+        }
+    }
 }
 
 impl CameraWindowState {
@@ -87,9 +238,53 @@ impl EditorWindow for CameraWindow {
     type State = CameraWindowState;
 
     const NAME: &'static str = "Cameras";
+    const CATEGORY: &'static str = "Viewport";
+    const ICON: Option<&'static str> = Some("📷");
 
-    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
         cameras_ui(ui, world);
+        ui.separator();
+        let state = cx.state_mut::<CameraWindow>().unwrap();
+        ui.horizontal(|ui| {
+            ui.label("Camera switch transition");
+            ui.add(
+                egui::DragValue::new(&mut state.transition_duration)
+                    .range(0.0..=5.0)
+                    .speed(0.01)
+                    .suffix(" s"),
+            );
+        });
+        let mut follow = state.follow.is_some();
+        let follow_toggle = ui.checkbox(&mut follow, "Follow selected").on_hover_text(
+            "Keep the selected entity centered, until it is deselected or Escape is pressed",
+        );
+        if follow_toggle.changed() {
+            state.follow = match follow {
+                true => world.resource::<Selection>().last(),
+                false => None,
+            };
+        }
+        ui.separator();
+        camera_speeds_ui(ui, &mut state.speeds);
+        ui.separator();
+        bookmarks::bookmarks_ui(world, cx.state_mut::<CameraWindow>().unwrap(), ui);
+        ui.separator();
+        views::views_ui(world, &mut cx, ui);
+    }
+
+    fn save_state(state: &Self::State) -> Option<String> {
+        ron::to_string(&state.speeds).ok()
+    }
+
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Ok(speeds) = ron::from_str(saved) {
+            state.speeds = speeds;
+        }
+    }
+
+    fn viewport_ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let state = cx.state_mut::<CameraWindow>().unwrap();
+        navigation::navigation_gizmo_ui(world, state, ui);
     }
 
     fn viewport_toolbar_ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
@@ -98,22 +293,30 @@ impl EditorWindow for CameraWindow {
             for camera in EditorCamKind::all() {
                 ui.horizontal(|ui| {
                     if ui.button(camera.name()).clicked() {
-                        if state.editor_cam != camera {
-                            set_active_editor_camera_marker(world, camera);
-                        }
-
-                        state.editor_cam = camera;
-
+                        CameraWindow::set_active_camera(world, state, camera);
                         ui.close_menu();
                     }
                 });
             }
         });
         ui.checkbox(&mut state.show_ui, "UI");
+
+        let screenshot = ui
+            .button("📷")
+            .on_hover_text("Save a screenshot of the viewport");
+        if screenshot.clicked() {
+            let folder = cx
+                .state::<DebugSettingsWindow>()
+                .map_or("", |settings| settings.screenshot_folder.as_str());
+            screenshot::save_viewport_screenshot(world, folder);
+        }
     }
 
     fn app_setup(app: &mut App) {
-        app.init_resource::<PreviouslyActiveCameras>();
+        screenshot::setup(app);
+        app.init_resource::<PreviouslyActiveCameras>()
+            .init_resource::<CameraFocus>()
+            .init_resource::<CameraTransition>();
 
         app.add_plugins(camera_2d_panzoom::PanCamPlugin)
             .add_plugins(camera_3d_free::FlycamPlugin)
@@ -126,19 +329,83 @@ impl EditorWindow for CameraWindow {
                     .before(camera_2d_panzoom::CameraSystem::EditorCam2dPanZoom),
             )
             .add_systems(PreUpdate, toggle_editor_cam)
-            .add_systems(PreUpdate, focus_selected)
-            .add_systems(Update, initial_camera_setup);
+            .add_systems(Update, scroll_fly_speed.before(set_editor_cam_active))
+            .add_systems(PreUpdate, bookmarks::bookmark_hotkeys)
+            .add_systems(
+                PreUpdate,
+                (
+                    focus_selected,
+                    animate_camera_focus,
+                    animate_camera_transition,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, initial_camera_setup)
+            .add_systems(
+                Update,
+                follow_entity
+                    .after(camera_3d_panorbit::CameraSystem::EditorCam3dPanOrbit)
+                    .after(camera_3d_free::CameraSystem::EditorCam3dFree)
+                    .after(camera_2d_panzoom::CameraSystem::EditorCam2dPanZoom),
+            );
         app.add_systems(PreStartup, spawn_editor_cameras);
 
         app.add_systems(
             PostUpdate,
-            set_main_pass_viewport
+            (retarget_editor_cameras, set_main_pass_viewport)
                 .after(bevy_editor_pls_core::EditorSet::UI)
                 .before(bevy::render::camera::CameraUpdateSystem),
         );
     }
 }
 
+const MIN_FLY_SPEED: f32 = 0.1;
+const MAX_FLY_SPEED: f32 = 1000.0;
+
+fn camera_speeds_ui(ui: &mut egui::Ui, speeds: &mut CameraSpeeds) {
+    egui::Grid::new("camera speeds").show(ui, |ui| {
+        ui.label("Fly speed");
+        ui.add(egui::Slider::new(&mut speeds.fly, MIN_FLY_SPEED..=MAX_FLY_SPEED).logarithmic(true))
+            .on_hover_text("Scroll while looking around with the free camera to change it");
+        ui.end_row();
+
+        for (name, speed) in [
+            ("Pan speed", &mut speeds.pan),
+            ("Orbit speed", &mut speeds.orbit),
+            ("Zoom speed", &mut speeds.zoom),
+        ] {
+            ui.label(name);
+            ui.add(egui::Slider::new(speed, 0.1..=10.0).logarithmic(true));
+            ui.end_row();
+        }
+    });
+}
+
+/// Scrolling while looking around with the free camera changes its speed
+fn scroll_fly_speed(
+    mut editor: ResMut<Editor>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut scroll_events: EventReader<MouseWheel>,
+    flycam: Query<&camera_3d_free::FlycamControls>,
+) {
+    let scroll: f32 = scroll_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum();
+    let looking = flycam.get_single().is_ok_and(|flycam| flycam.enable_look);
+    if scroll == 0.0 || !looking || !mouse_input.pressed(MouseButton::Right) {
+        return;
+    }
+
+    let Some(state) = editor.window_state_mut::<CameraWindow>() else {
+        return;
+    };
+    state.speeds.fly = (state.speeds.fly * 1.2f32.powf(scroll)).clamp(MIN_FLY_SPEED, MAX_FLY_SPEED);
+}
+
 fn set_active_editor_camera_marker(world: &mut World, editor_cam: EditorCamKind) {
     let mut previously_active = world.query_filtered::<Entity, With<ActiveEditorCamera>>();
     let mut previously_active_iter = previously_active.iter(world);
@@ -168,6 +435,14 @@ fn set_active_editor_camera_marker(world: &mut World, editor_cam: EditorCamKind)
             let mut state = world.query_filtered::<Entity, With<EditorCamera3dPanOrbit>>();
             state.iter(world).next().unwrap()
         }
+        EditorCamKind::D3OrthoTop
+        | EditorCamKind::D3OrthoFront
+        | EditorCamKind::D3OrthoBack
+        | EditorCamKind::D3OrthoLeft
+        | EditorCamKind::D3OrthoRight => {
+            let mut state = world.query_filtered::<Entity, With<EditorCamera3dOrthographic>>();
+            state.iter(world).next().unwrap()
+        }
     };
     world.entity_mut(entity).insert(ActiveEditorCamera);
 }
@@ -260,6 +535,38 @@ fn spawn_editor_cameras(mut commands: Commands, editor: Res<Editor>) {
         render_layers.clone(),
     ));
 
+    commands
+        .spawn((
+            Camera3dBundle {
+                camera: Camera {
+                    //  Prevent multiple cameras from having the same priority.
+                    order: editor_cam_priority + 3,
+                    target: target.clone(),
+                    is_active: false,
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, ORTHOGRAPHIC_DISTANCE, 0.0)
+                    .looking_at(Vec3::ZERO, Vec3::NEG_Z),
+                ..Camera3dBundle::default()
+            },
+            Ec3d,
+            camera_2d_panzoom::PanCamControls::default(),
+            EditorCamera,
+            EditorCamera3dOrthographic,
+            HideInEditor,
+            Name::new("Editor Camera 3D Orthographic"),
+            NotInScene,
+            GizmoCamera,
+            render_layers.clone(),
+        ))
+        // the pan/zoom controls operate on an `OrthographicProjection` component
+        .remove::<Projection>()
+        .insert(OrthographicProjection {
+            far: 2.0 * ORTHOGRAPHIC_DISTANCE,
+            scale: 0.02,
+            ..default()
+        });
+
     commands.spawn((
         Camera2dBundle {
             camera: Camera {
@@ -289,12 +596,28 @@ fn set_editor_cam_active(
     mut editor_cameras: ParamSet<(
         Query<(&mut Camera, &mut camera_3d_free::FlycamControls)>,
         Query<(&mut Camera, &mut camera_3d_panorbit::PanOrbitCamera)>,
-        Query<(&mut Camera, &mut camera_2d_panzoom::PanCamControls)>,
+        Query<(&mut Camera, &mut camera_2d_panzoom::PanCamControls), With<EditorCamera2dPanZoom>>,
+        Query<
+            (&mut Camera, &mut camera_2d_panzoom::PanCamControls),
+            With<EditorCamera3dOrthographic>,
+        >,
     )>,
+    gizmo_targets: Query<&GizmoTarget>,
+    transition: Res<CameraTransition>,
     // mut ui_camera_settings: Query<&mut UiCameraConfig, With<EditorCamera>>,
 ) {
     let camera_window_state = &editor.window_state::<CameraWindow>().unwrap();
     let editor_cam = camera_window_state.editor_cam;
+    let speeds = camera_window_state.speeds;
+    // in passive mode the game keeps rendering the viewport with its own cameras
+    let editor_cams_active = editor.active() && !editor.passive_input();
+    // dragging a gizmo handle shouldn't also move the camera
+    let gizmo_used = gizmo_targets
+        .iter()
+        .any(|target| target.is_focused() || target.is_active());
+    let viewport_interaction_active = editor.viewport_interaction_active() && !gizmo_used;
+    // the controllers take over once the camera switch transition is done
+    let transitioning = transition.0.is_some();
 
     // if editor.active() {
     // ui_camera_settings
@@ -304,24 +627,38 @@ fn set_editor_cam_active(
     {
         let mut q = editor_cameras.p0();
         let mut editor_cam_3d_free = q.single_mut();
-        let active = matches!(editor_cam, EditorCamKind::D3Free) && editor.active();
+        let active = matches!(editor_cam, EditorCamKind::D3Free) && editor_cams_active;
         editor_cam_3d_free.0.is_active = active;
-        editor_cam_3d_free.1.enable_movement = active && !editor.listening_for_text();
-        editor_cam_3d_free.1.enable_look = active && editor.viewport_interaction_active();
+        editor_cam_3d_free.1.speed = speeds.fly;
+        editor_cam_3d_free.1.enable_movement =
+            active && !editor.listening_for_text() && !transitioning;
+        editor_cam_3d_free.1.enable_look = active && viewport_interaction_active && !transitioning;
     }
     {
         let mut q = editor_cameras.p1();
         let mut editor_cam_3d_panorbit = q.single_mut();
-        let active = matches!(editor_cam, EditorCamKind::D3PanOrbit) && editor.active();
+        let active = matches!(editor_cam, EditorCamKind::D3PanOrbit) && editor_cams_active;
         editor_cam_3d_panorbit.0.is_active = active;
-        editor_cam_3d_panorbit.1.enabled = active && editor.viewport_interaction_active();
+        editor_cam_3d_panorbit.1.pan_speed = speeds.pan;
+        editor_cam_3d_panorbit.1.orbit_speed = speeds.orbit;
+        editor_cam_3d_panorbit.1.zoom_speed = speeds.zoom;
+        editor_cam_3d_panorbit.1.enabled = active && viewport_interaction_active && !transitioning;
     }
     {
         let mut q = editor_cameras.p2();
         let mut editor_cam_2d_panzoom = q.single_mut();
-        let active = matches!(editor_cam, EditorCamKind::D2PanZoom) && editor.active();
+        let active = matches!(editor_cam, EditorCamKind::D2PanZoom) && editor_cams_active;
         editor_cam_2d_panzoom.0.is_active = active;
-        editor_cam_2d_panzoom.1.enabled = active && editor.viewport_interaction_active();
+        editor_cam_2d_panzoom.1.enabled = active && viewport_interaction_active;
+    }
+    {
+        let mut q = editor_cameras.p3();
+        let mut editor_cam_3d_orthographic = q.single_mut();
+        let active = editor_cam.orthographic_view().is_some() && editor_cams_active;
+        editor_cam_3d_orthographic.0.is_active = active;
+        editor_cam_3d_orthographic.1.zoom_speed = speeds.zoom;
+        editor_cam_3d_orthographic.1.enabled =
+            active && viewport_interaction_active && !transitioning;
     }
 }
 
@@ -334,7 +671,7 @@ fn toggle_editor_cam(
     mut prev_active_cams: ResMut<PreviouslyActiveCameras>,
     mut cam_query: Query<(Entity, &mut Camera)>,
 ) {
-    if editor.always_active() {
+    if editor.always_active() || editor.passive_input() {
         //Prevent accumulation of irrelevant events
         editor_events.clear();
         return;
@@ -365,13 +702,29 @@ fn toggle_editor_cam(
     }
 }
 
+/// Where [`focus_selected`] is moving the active editor camera to
+#[derive(Resource, Default)]
+struct CameraFocus(Option<CameraFocusTarget>);
+
+struct CameraFocusTarget {
+    translation: Vec3,
+    /// Focus and radius of the pan/orbit camera
+    pan_orbit: (Vec3, f32),
+    orthographic_scale: f32,
+}
+
+/// How quickly the camera approaches the focus target, higher is faster
+const FOCUS_SMOOTHNESS: f32 = 12.0;
+
+#[allow(clippy::too_many_arguments)]
 fn focus_selected(
     mut editor_events: EventReader<EditorEvent>,
-    mut active_cam: Query<
+    mut camera_focus: ResMut<CameraFocus>,
+    active_cam: Query<
         (
-            &mut Transform,
-            Option<&mut PanOrbitCamera>,
-            Option<&mut OrthographicProjection>,
+            &Transform,
+            Option<&Projection>,
+            Option<&OrthographicProjection>,
         ),
         With<ActiveEditorCamera>,
     >,
@@ -379,7 +732,18 @@ fn focus_selected(
         (&GlobalTransform, Option<&Aabb>, Option<&Sprite>),
         Without<ActiveEditorCamera>,
     >,
+    // ui nodes are laid out in screen space, so they'd throw off the bounds
+    scene_query: Query<
+        Entity,
+        (
+            With<GlobalTransform>,
+            Without<NotInScene>,
+            Without<HideInEditor>,
+            Without<Node>,
+        ),
+    >,
     editor: Res<Editor>,
+    selection: Res<Selection>,
     window: Query<&Window>,
 ) {
     let Ok(window) = window.get(editor.window()) else {
@@ -389,21 +753,25 @@ fn focus_selected(
     };
 
     for event in editor_events.read() {
-        match *event {
-            EditorEvent::FocusSelected => (),
+        let entities: Vec<Entity> = match *event {
+            EditorEvent::FocusSelected => selection.iter().collect(),
+            EditorEvent::FrameAll => scene_query.iter().collect(),
             _ => continue,
-        }
+        };
 
-        let hierarchy = editor.window_state::<HierarchyWindow>().unwrap();
-        if hierarchy.selected.is_empty() {
-            info!("Coudldn't focus on selection because selection is empty");
+        if entities.is_empty() {
+            match *event {
+                EditorEvent::FrameAll => {
+                    info!("Couldn't frame all entities because there are none")
+                }
+                _ => info!("Coudldn't focus on selection because selection is empty"),
+            }
             return;
         }
 
-        let (bounds_min, bounds_max) = hierarchy
-            .selected
+        let (bounds_min, bounds_max) = entities
             .iter()
-            .filter_map(|selected_e| {
+            .filter_map(|&selected_e| {
                 selected_query
                     .get(selected_e)
                     .map(|(&tf, bounds, sprite)| {
@@ -438,28 +806,238 @@ fn focus_selected(
             RADIUS_MULTIPLIER
         };
 
-        let (mut camera_tf, pan_orbit_cam, ortho) = active_cam.single_mut();
-
-        if let Some(mut ortho) = ortho {
-            camera_tf.translation.x = focus_loc.x;
-            camera_tf.translation.y = focus_loc.y;
+        let Ok((camera_tf, projection, ortho)) = active_cam.get_single() else {
+            return;
+        };
 
-            ortho.scale = radius / window.width().min(window.height()).max(1.0);
+        let (translation, radius, orthographic_scale) = if ortho.is_some() {
+            // keep the distance along the view direction, which only matters for clipping
+            let back = camera_tf.back();
+            (
+                focus_loc + back * (camera_tf.translation - focus_loc).dot(*back),
+                radius,
+                radius / window.width().min(window.height()).max(1.0),
+            )
         } else {
-            camera_tf.translation = focus_loc + camera_tf.rotation.mul_vec3(Vec3::Z) * radius;
-        }
-
-        if let Some(mut pan_orbit_cam) = pan_orbit_cam {
-            pan_orbit_cam.focus = focus_loc;
-            pan_orbit_cam.radius = radius;
-        }
+            // back off far enough for the bounding sphere to fit into the narrower field of view
+            let radius = match projection {
+                Some(Projection::Perspective(perspective)) => {
+                    let half_fov_y = perspective.fov * 0.5;
+                    let half_fov_x = (half_fov_y.tan() * perspective.aspect_ratio).atan();
+                    let half_fov = half_fov_x.min(half_fov_y).max(f32::EPSILON);
+                    let sphere_radius = bounds_size.length() * 0.5;
+                    if sphere_radius > f32::EPSILON {
+                        sphere_radius / half_fov.sin()
+                    } else {
+                        radius
+                    }
+                }
+                _ => radius,
+            };
+            (
+                focus_loc + camera_tf.rotation.mul_vec3(Vec3::Z) * radius,
+                radius,
+                1.0,
+            )
+        };
+        camera_focus.0 = Some(CameraFocusTarget {
+            translation,
+            pan_orbit: (focus_loc, radius),
+            orthographic_scale,
+        });
 
-        let len = hierarchy.selected.len();
+        let len = entities.len();
         let noun = if len == 1 { "entity" } else { "entities" };
         info!("Focused on {} {}", len, noun);
     }
 }
 
+/// Moves the active editor camera towards the [`CameraFocus`] target
+fn animate_camera_focus(
+    mut camera_focus: ResMut<CameraFocus>,
+    time: Res<Time<Real>>,
+    mut active_cam: Query<
+        (
+            &mut Transform,
+            Option<&mut PanOrbitCamera>,
+            Option<&mut OrthographicProjection>,
+        ),
+        With<ActiveEditorCamera>,
+    >,
+) {
+    let Some(target) = &camera_focus.0 else {
+        return;
+    };
+    let Ok((mut camera_tf, pan_orbit_cam, ortho)) = active_cam.get_single_mut() else {
+        camera_focus.0 = None;
+        return;
+    };
+
+    // framerate independent exponential smoothing
+    let t = 1.0 - (-FOCUS_SMOOTHNESS * time.delta_seconds()).exp();
+    let (focus, radius) = target.pan_orbit;
+    let scale_done = ortho.as_ref().is_none_or(|ortho| {
+        (ortho.scale - target.orthographic_scale).abs() < target.orthographic_scale * 1e-3
+    });
+    let done = scale_done && camera_tf.translation.distance(target.translation) < radius * 1e-3;
+    let t = if done { 1.0 } else { t };
+
+    camera_tf.translation = camera_tf.translation.lerp(target.translation, t);
+    if let Some(mut ortho) = ortho {
+        ortho.scale += (target.orthographic_scale - ortho.scale) * t;
+    }
+    if let Some(mut pan_orbit_cam) = pan_orbit_cam {
+        pan_orbit_cam.focus = pan_orbit_cam.focus.lerp(focus, t);
+        pan_orbit_cam.radius += (radius - pan_orbit_cam.radius) * t;
+    }
+
+    if done {
+        camera_focus.0 = None;
+    }
+}
+
+/// Moves the newly active editor camera from the previous camera's transform
+/// after switching in [`CameraWindow::set_active_camera`]
+#[derive(Resource, Default)]
+struct CameraTransition(Option<CameraTransitionState>);
+
+struct CameraTransitionState {
+    camera: Entity,
+    from: Transform,
+    to: Transform,
+    elapsed: f32,
+    duration: f32,
+}
+
+/// Moves the active editor camera to `transform` and restores the state of its controller.
+///
+/// A running camera switch transition is redirected to `transform`, otherwise the camera
+/// is animated there over `duration` seconds, or placed there right away if that is zero.
+fn move_active_camera(
+    world: &mut World,
+    transform: Transform,
+    pan_orbit: Option<(Vec3, f32)>,
+    orthographic_scale: Option<f32>,
+    duration: f32,
+) {
+    let mut active_camera = world.query_filtered::<(
+        Entity,
+        &mut Transform,
+        Option<&mut PanOrbitCamera>,
+        Option<&mut OrthographicProjection>,
+        Option<&mut camera_3d_free::FlycamControls>,
+    ), With<ActiveEditorCamera>>();
+    let Ok((entity, current, pan_orbit_cam, orthographic, flycam)) =
+        active_camera.get_single_mut(world)
+    else {
+        return;
+    };
+
+    let from = *current;
+    if let (Some(mut pan_orbit_cam), Some((focus, radius))) = (pan_orbit_cam, pan_orbit) {
+        pan_orbit_cam.focus = focus;
+        pan_orbit_cam.radius = radius;
+    }
+    if let (Some(mut orthographic), Some(scale)) = (orthographic, orthographic_scale) {
+        orthographic.scale = scale;
+    }
+    if let Some(mut flycam) = flycam {
+        let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        flycam.yaw = yaw;
+        flycam.pitch = pitch;
+    }
+
+    let mut transition = world.resource_mut::<CameraTransition>();
+    if let Some(running) = &mut transition.0 {
+        running.to = transform;
+    } else if duration > 0.0 {
+        transition.0 = Some(CameraTransitionState {
+            camera: entity,
+            from,
+            to: transform,
+            elapsed: 0.0,
+            duration,
+        });
+    } else if let Some(mut current) = world.get_mut::<Transform>(entity) {
+        *current = transform;
+    }
+}
+
+fn animate_camera_transition(
+    mut transition: ResMut<CameraTransition>,
+    time: Res<Time<Real>>,
+    mut cameras: Query<&mut Transform, With<EditorCamera>>,
+) {
+    let Some(state) = &mut transition.0 else {
+        return;
+    };
+    let Ok(mut transform) = cameras.get_mut(state.camera) else {
+        transition.0 = None;
+        return;
+    };
+
+    state.elapsed += time.delta_seconds();
+    let t = (state.elapsed / state.duration).min(1.0);
+    // smoothstep, to ease in and out
+    let t = t * t * (3.0 - 2.0 * t);
+
+    transform.translation = state.from.translation.lerp(state.to.translation, t);
+    transform.rotation = state.from.rotation.slerp(state.to.rotation, t);
+    transform.scale = state.from.scale.lerp(state.to.scale, t);
+
+    if state.elapsed >= state.duration {
+        *transform = state.to;
+        transition.0 = None;
+    }
+}
+
+/// Keeps the [`CameraWindowState::follow`] entity centered, by moving the pan/orbit camera's pivot
+/// to it or moving the other cameras along with it.
+fn follow_entity(
+    mut editor: ResMut<Editor>,
+    selection: Res<Selection>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    targets: Query<&GlobalTransform, Without<EditorCamera>>,
+    mut active_cam: Query<(&mut Transform, Option<&mut PanOrbitCamera>), With<ActiveEditorCamera>>,
+    mut last_position: Local<Option<Vec3>>,
+) {
+    let Some(followed) = editor
+        .window_state::<CameraWindow>()
+        .and_then(|state| state.follow)
+    else {
+        *last_position = None;
+        return;
+    };
+
+    let cancelled = keyboard_input.just_pressed(KeyCode::Escape) && !editor.listening_for_text();
+    let position = targets.get(followed).ok().map(GlobalTransform::translation);
+    let position = match position {
+        Some(position) if selection.contains(followed) && !cancelled => position,
+        _ => {
+            if let Some(state) = editor.window_state_mut::<CameraWindow>() {
+                state.follow = None;
+            }
+            *last_position = None;
+            return;
+        }
+    };
+
+    if let Ok((mut transform, pan_orbit)) = active_cam.get_single_mut() {
+        match pan_orbit {
+            Some(mut pan_orbit) => {
+                pan_orbit.focus = position;
+                transform.translation = position + transform.rotation * Vec3::Z * pan_orbit.radius;
+            }
+            None => {
+                if let Some(last_position) = *last_position {
+                    transform.translation += position - last_position;
+                }
+            }
+        }
+    }
+    *last_position = Some(position);
+}
+
 fn initial_camera_setup(
     mut has_decided_initial_cam: Local<bool>,
     mut was_positioned_3d: Local<bool>,
@@ -568,22 +1146,38 @@ fn initial_camera_setup(
     }
 }
 
-fn set_main_pass_viewport(
-    egui_settings: Res<bevy_inspector_egui::bevy_egui::EguiSettings>,
+/// Moves the editor cameras along when the editor moves to another window, e.g. because its window was closed
+fn retarget_editor_cameras(
     editor: Res<Editor>,
-    window: Query<&Window>,
     mut cameras: Query<&mut Camera, With<EditorCamera>>,
 ) {
     if !editor.is_changed() {
         return;
-    };
+    }
 
-    let Ok(window) = window.get(editor.window()) else {
+    let window = editor.window();
+    for mut camera in &mut cameras {
+        let on_window = matches!(
+            camera.target,
+            RenderTarget::Window(WindowRef::Entity(target)) if target == window
+        );
+        if !on_window {
+            camera.target = RenderTarget::Window(WindowRef::Entity(window));
+        }
+    }
+}
+
+fn set_main_pass_viewport(
+    editor: Res<Editor>,
+    mut cameras: Query<&mut Camera, With<EditorCamera>>,
+) {
+    if !editor.is_changed() {
         return;
     };
 
     let viewport = editor.active().then(|| {
-        let scale_factor = window.scale_factor() * egui_settings.scale_factor;
+        // the scale the viewport was laid out with, `EguiSettings` may already have changed
+        let scale_factor = editor.pixels_per_point();
 
         let mut viewport_pos = editor.viewport().left_top().to_vec2() * scale_factor;
         let mut viewport_size = editor.viewport().size() * scale_factor;
@@ -608,3 +1202,31 @@ fn set_main_pass_viewport(
         .iter_mut()
         .for_each(|mut cam| cam.viewport.clone_from(&viewport));
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn editor_cameras_follow_the_editor_window() {
+        let mut world = World::new();
+        let closed_window = world.spawn_empty().id();
+        let window = world.spawn_empty().id();
+        let camera = Camera {
+            target: RenderTarget::Window(WindowRef::Entity(closed_window)),
+            ..default()
+        };
+        let camera = world.spawn((camera, EditorCamera)).id();
+        world.insert_resource(Editor::new(window, false));
+
+        world.run_system_once(retarget_editor_cameras);
+
+        let target = &world.get::<Camera>(camera).unwrap().target;
+        assert!(matches!(
+            *target,
+            RenderTarget::Window(WindowRef::Entity(target)) if target == window
+        ));
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::egui;
+
+use super::{ActiveEditorCamera, CameraWindow, CameraWindowState, EditorCamKind};
+
+/// Radius of the navigation gizmo in the viewport corner, in egui points
+const GIZMO_RADIUS: f32 = 40.0;
+/// Radius of the clickable circles at the ends of the axes
+const HANDLE_RADIUS: f32 = 8.0;
+/// Distance between the gizmo and the edges of the viewport
+const GIZMO_MARGIN: f32 = 8.0;
+
+const AXES: [(Vec3, egui::Color32, &str); 3] = [
+    (Vec3::X, egui::Color32::from_rgb(230, 70, 80), "X"),
+    (Vec3::Y, egui::Color32::from_rgb(120, 200, 40), "Y"),
+    (Vec3::Z, egui::Color32::from_rgb(60, 130, 240), "Z"),
+];
+
+/// The orthographic view looking at the focus from the `direction` axis
+fn view_along(direction: Vec3) -> Option<EditorCamKind> {
+    EditorCamKind::all()
+        .into_iter()
+        .find(|camera| camera.orthographic_view().map(|(view, _)| view) == Some(direction))
+}
+
+/// Shows the world axes as seen by the active editor camera in the top right corner of the viewport.
+/// Clicking the end of an axis switches to the orthographic view from that side.
+///
+/// It is drawn on a foreground layer, so hovering it keeps clicks from reaching the viewport.
+pub(super) fn navigation_gizmo_ui(
+    world: &mut World,
+    state: &mut CameraWindowState,
+    ui: &mut egui::Ui,
+) {
+    if state.editor_cam == EditorCamKind::D2PanZoom {
+        return;
+    }
+    let mut active_camera = world.query_filtered::<&GlobalTransform, With<ActiveEditorCamera>>();
+    let Ok(camera_transform) = active_camera.get_single(world) else {
+        return;
+    };
+    let to_view = camera_transform.compute_transform().rotation.inverse();
+
+    let size = egui::Vec2::splat(2.0 * GIZMO_RADIUS);
+    let viewport = ui.available_rect_before_wrap();
+    let pos = viewport.right_top() + egui::vec2(-size.x - GIZMO_MARGIN, GIZMO_MARGIN);
+
+    let clicked = egui::Area::new(ui.id().with("navigation_gizmo"))
+        .order(egui::Order::Foreground)
+        .fixed_pos(pos)
+        .show(ui.ctx(), |ui| {
+            let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+            let painter = ui.painter();
+            let center = rect.center();
+            if response.contains_pointer() {
+                let fill = ui.visuals().extreme_bg_color.gamma_multiply(0.5);
+                painter.circle_filled(center, GIZMO_RADIUS, fill);
+            }
+
+            // both ends of every axis, drawn from back to front
+            let mut handles: Vec<_> = AXES
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &(axis, color, label))| {
+                    [(i, axis, color, Some(label)), (i, -axis, color, None)]
+                })
+                .map(|(i, axis, color, label)| {
+                    let view = to_view * axis;
+                    let offset = egui::vec2(view.x, -view.y) * (GIZMO_RADIUS - HANDLE_RADIUS);
+                    let id = ui
+                        .id()
+                        .with(("navigation_gizmo_handle", i, label.is_some()));
+                    (id, axis, view.z, center + offset, color, label)
+                })
+                .collect();
+            handles.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+            let mut clicked = None;
+            for (id, axis, _, handle_pos, color, label) in handles {
+                let view = view_along(axis);
+                let handle_size = egui::Vec2::splat(2.0 * HANDLE_RADIUS);
+                let handle_rect = egui::Rect::from_center_size(handle_pos, handle_size);
+                let handle = match view {
+                    Some(view) => ui
+                        .interact(handle_rect, id, egui::Sense::click())
+                        .on_hover_text(view.name()),
+                    None => ui.interact(handle_rect, id, egui::Sense::hover()),
+                };
+                if handle.clicked() {
+                    clicked = view;
+                }
+
+                let painter = ui.painter();
+                let highlight = handle.hovered() && view.is_some();
+                let Some(label) = label else {
+                    let fill = color.gamma_multiply(if highlight { 0.8 } else { 0.3 });
+                    painter.circle(
+                        handle_pos,
+                        HANDLE_RADIUS,
+                        fill,
+                        egui::Stroke::new(1.0, color),
+                    );
+                    continue;
+                };
+                painter.line_segment([center, handle_pos], egui::Stroke::new(2.0, color));
+                painter.circle_filled(handle_pos, HANDLE_RADIUS, color);
+                let text_color = match highlight {
+                    true => egui::Color32::WHITE,
+                    false => egui::Color32::BLACK,
+                };
+                painter.text(
+                    handle_pos,
+                    egui::Align2::CENTER_CENTER,
+                    label,
+                    egui::FontId::proportional(11.0),
+                    text_color,
+                );
+            }
+            clicked
+        })
+        .inner;
+
+    if let Some(view) = clicked {
+        CameraWindow::set_active_camera(world, state, view);
+    }
+}
@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::render_asset::{RenderAssetUsages, RenderAssets};
+use bevy::render::render_resource::{
+    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+    ImageDataLayout, Maintain, MapMode, TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::render::renderer::{RenderDevice, RenderQueue};
+use bevy::render::texture::GpuImage;
+use bevy::render::view::RenderLayers;
+use bevy::render::{Extract, ExtractSchedule, Render, RenderApp, RenderSet};
+use bevy::tasks::IoTaskPool;
+use bevy::utils::HashSet;
+
+use super::{ActiveEditorCamera, EDITOR_RENDER_LAYER};
+use crate::hierarchy::HideInEditor;
+use crate::scenes::NotInScene;
+
+type ScreenshotError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A copy of the active editor camera rendering into `image`, despawned once the image was saved
+struct PendingScreenshot {
+    camera: Entity,
+    image: Handle<Image>,
+    path: PathBuf,
+    /// Frames since the camera was spawned. It only renders the scene from the frame after,
+    /// once visibility was computed for it.
+    frames: u32,
+}
+
+#[derive(Resource)]
+struct ViewportScreenshots {
+    pending: Vec<PendingScreenshot>,
+    /// Images the render world read back
+    saved: Mutex<Receiver<AssetId<Image>>>,
+}
+
+/// Render world side of [`ViewportScreenshots`]
+#[derive(Resource)]
+struct ScreenshotReadback {
+    pending: Vec<(AssetId<Image>, PathBuf)>,
+    /// Read back already, remembered until the main world removed them so they are saved only once
+    saved: HashSet<AssetId<Image>>,
+    sender: Sender<AssetId<Image>>,
+}
+
+pub(super) fn setup(app: &mut App) {
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    let (sender, receiver) = std::sync::mpsc::channel();
+    render_app
+        .insert_resource(ScreenshotReadback {
+            pending: Vec::new(),
+            saved: HashSet::default(),
+            sender,
+        })
+        .add_systems(ExtractSchedule, extract_screenshots)
+        .add_systems(Render, read_back_screenshots.in_set(RenderSet::Cleanup));
+
+    app.insert_resource(ViewportScreenshots {
+        pending: Vec::new(),
+        saved: Mutex::new(receiver),
+    })
+    .add_systems(Update, finish_screenshots);
+}
+
+/// Saves what the active editor camera sees in the viewport as a timestamped PNG in `folder`.
+///
+/// The scene is rendered again into an image by a copy of the camera, so the editor UI
+/// and everything on the [`EDITOR_RENDER_LAYER`] is left out.
+pub(super) fn save_viewport_screenshot(world: &mut World, folder: &str) {
+    if !world.contains_resource::<ViewportScreenshots>() {
+        warn!("Can't take a screenshot without the `RenderPlugin`");
+        return;
+    }
+    let mut active_camera = world.query_filtered::<(Entity, &Camera), With<ActiveEditorCamera>>();
+    let Ok((entity, camera)) = active_camera.get_single(world) else {
+        warn!("Can't take a screenshot without an active editor camera");
+        return;
+    };
+    let Some(size) = camera.physical_viewport_size() else {
+        warn!("Can't take a screenshot before the editor camera rendered");
+        return;
+    };
+    let camera = camera.clone();
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0; 4],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage |= TextureUsages::COPY_SRC | TextureUsages::RENDER_ATTACHMENT;
+    let image = world.resource_mut::<Assets<Image>>().add(image);
+
+    let camera = Camera {
+        target: RenderTarget::Image(image.clone()),
+        viewport: None,
+        is_active: true,
+        ..camera
+    };
+    let camera_ref = world.entity(entity);
+    let is_2d = camera_ref.contains::<Camera2d>();
+    let transform = camera_ref.get::<Transform>().copied().unwrap_or_default();
+    let global_transform = camera_ref
+        .get::<GlobalTransform>()
+        .copied()
+        .unwrap_or_default();
+    let perspective = camera_ref.get::<Projection>().cloned();
+    let orthographic = camera_ref.get::<OrthographicProjection>().cloned();
+    let render_layers = camera_ref
+        .get::<RenderLayers>()
+        .cloned()
+        .unwrap_or_default()
+        .without(EDITOR_RENDER_LAYER);
+
+    let mut screenshot_camera = match is_2d {
+        true => world.spawn(Camera2dBundle {
+            camera,
+            transform,
+            global_transform,
+            ..default()
+        }),
+        false => world.spawn(Camera3dBundle {
+            camera,
+            transform,
+            global_transform,
+            ..default()
+        }),
+    };
+    if let Some(perspective) = perspective {
+        screenshot_camera.insert(perspective);
+    }
+    // like the orthographic editor camera, which has no `Projection`
+    if let Some(orthographic) = orthographic {
+        screenshot_camera
+            .remove::<Projection>()
+            .insert(orthographic);
+    }
+    screenshot_camera.insert((
+        render_layers,
+        HideInEditor,
+        NotInScene,
+        Name::new("Viewport Screenshot Camera"),
+    ));
+    let camera = screenshot_camera.id();
+
+    world
+        .resource_mut::<ViewportScreenshots>()
+        .pending
+        .push(PendingScreenshot {
+            camera,
+            image,
+            path: screenshot_path(folder),
+            frames: 0,
+        });
+}
+
+/// Despawns the cameras of screenshots the render world read back
+fn finish_screenshots(
+    mut commands: Commands,
+    mut screenshots: ResMut<ViewportScreenshots>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let screenshots = &mut *screenshots;
+    let saved: Vec<_> = screenshots.saved.lock().unwrap().try_iter().collect();
+    screenshots.pending.retain_mut(|pending| {
+        if !saved.contains(&pending.image.id()) {
+            pending.frames += 1;
+            return true;
+        }
+        commands.entity(pending.camera).despawn();
+        images.remove(&pending.image);
+        false
+    });
+}
+
+fn extract_screenshots(
+    mut readback: ResMut<ScreenshotReadback>,
+    screenshots: Extract<Res<ViewportScreenshots>>,
+) {
+    let readback = &mut *readback;
+    let pending = screenshots
+        .pending
+        .iter()
+        .filter(|pending| pending.frames > 0);
+    readback.pending = pending
+        .filter(|pending| !readback.saved.contains(&pending.image.id()))
+        .map(|pending| (pending.image.id(), pending.path.clone()))
+        .collect();
+    readback.saved.retain(|&id| {
+        screenshots
+            .pending
+            .iter()
+            .any(|pending| pending.image.id() == id)
+    });
+}
+
+/// Copies the rendered screenshot images to the CPU after the frame was rendered and saves them
+fn read_back_screenshots(
+    mut readback: ResMut<ScreenshotReadback>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let readback = &mut *readback;
+    for (id, path) in std::mem::take(&mut readback.pending) {
+        // only prepared in a later frame if the image was added after the asset events were sent
+        let Some(gpu_image) = images.get(id) else {
+            continue;
+        };
+        match read_texture(gpu_image, &render_device, &render_queue) {
+            Ok(image) => {
+                IoTaskPool::get()
+                    .spawn(async move {
+                        match save_image(image, &path) {
+                            Ok(()) => info!("Saved viewport screenshot to {}", path.display()),
+                            Err(error) => error!("Couldn't save viewport screenshot: {error}"),
+                        }
+                    })
+                    .detach();
+            }
+            Err(error) => error!("Couldn't read back viewport screenshot: {error}"),
+        }
+        readback.saved.insert(id);
+        let _ = readback.sender.send(id);
+    }
+}
+
+/// Copies the texture into a buffer and waits for the GPU to finish, as screenshots are rare
+fn read_texture(
+    gpu_image: &GpuImage,
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+) -> Result<Image, ScreenshotError> {
+    let size = Extent3d {
+        width: gpu_image.size.x,
+        height: gpu_image.size.y,
+        depth_or_array_layers: 1,
+    };
+    let bytes_per_row = gpu_image.size.x as usize * 4;
+    let padded_bytes_per_row = RenderDevice::align_copy_bytes_per_row(bytes_per_row);
+
+    let buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("viewport_screenshot_buffer"),
+        size: (padded_bytes_per_row * gpu_image.size.y as usize) as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("viewport_screenshot"),
+    });
+    encoder.copy_texture_to_buffer(
+        gpu_image.texture.as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row as u32),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+    render_queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    render_device.map_buffer(&slice, MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    render_device.poll(Maintain::Wait);
+    receiver.recv()??;
+
+    let data = slice
+        .get_mapped_range()
+        .chunks(padded_bytes_per_row)
+        .flat_map(|row| &row[..bytes_per_row])
+        .copied()
+        .collect();
+    buffer.unmap();
+
+    Ok(Image::new(
+        size,
+        TextureDimension::D2,
+        data,
+        gpu_image.texture_format,
+        RenderAssetUsages::MAIN_WORLD,
+    ))
+}
+
+fn screenshot_path(folder: &str) -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_millis());
+    Path::new(folder).join(format!("viewport-{millis}.png"))
+}
+
+fn save_image(image: Image, path: &Path) -> Result<(), ScreenshotError> {
+    let image = image.try_into_dynamic()?;
+
+    if let Some(folder) = path.parent() {
+        std::fs::create_dir_all(folder)?;
+    }
+    image.to_rgb8().save(path)?;
+    Ok(())
+}
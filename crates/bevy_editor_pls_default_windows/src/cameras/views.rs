@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+use bevy_editor_pls_core::{editor_window::EditorWindowContext, Selection};
+use bevy_inspector_egui::egui;
+
+use super::{
+    camera_3d_panorbit::PanOrbitCamera, move_active_camera, ActiveEditorCamera, CameraWindow,
+    EditorCamKind,
+};
+use crate::visibility::VisibilityWindow;
+
+/// A saved inspection context: the editor camera, the selection and the hidden visibility groups
+#[derive(Clone)]
+pub struct SceneView {
+    pub name: String,
+    pub camera: EditorCamKind,
+    pub transform: Transform,
+    /// Focus and radius of the pan/orbit camera
+    pub pan_orbit: Option<(Vec3, f32)>,
+    /// Scale of the 2d camera's projection
+    pub orthographic_scale: Option<f32>,
+    pub selected: Vec<Entity>,
+    pub hidden_groups: Vec<String>,
+}
+
+pub(super) fn views_ui(world: &mut World, cx: &mut EditorWindowContext, ui: &mut egui::Ui) {
+    let state = cx.state_mut::<CameraWindow>().unwrap();
+
+    ui.label("Views");
+
+    let mut save = false;
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut state.new_view_name)
+                .hint_text("View name")
+                .desired_width(120.0),
+        );
+        save = ui.button("Save view").clicked();
+    });
+
+    let mut apply = None;
+    let mut remove = None;
+    for (i, view) in state.views.iter().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.button(view.name.as_str()).clicked() {
+                apply = Some(i);
+            }
+            if ui.small_button("✖").on_hover_text("Delete view").clicked() {
+                remove = Some(i);
+            }
+        });
+    }
+
+    if save {
+        let mut name = std::mem::take(&mut state.new_view_name);
+        if name.is_empty() {
+            name = format!("View {}", state.views.len() + 1);
+        }
+        if let Some(view) = capture_view(world, cx, name) {
+            cx.state_mut::<CameraWindow>().unwrap().views.push(view);
+        }
+    }
+    if let Some(i) = apply {
+        let view = cx.state::<CameraWindow>().unwrap().views[i].clone();
+        apply_view(world, cx, &view);
+    }
+    if let Some(i) = remove {
+        cx.state_mut::<CameraWindow>().unwrap().views.remove(i);
+    }
+}
+
+/// Captures the current editor camera, selection and hidden visibility groups
+pub fn capture_view(
+    world: &mut World,
+    cx: &mut EditorWindowContext,
+    name: String,
+) -> Option<SceneView> {
+    let camera = cx.state::<CameraWindow>()?.editor_cam;
+
+    let mut active_camera = world.query_filtered::<(
+        &Transform,
+        Option<&PanOrbitCamera>,
+        Option<&OrthographicProjection>,
+    ), With<ActiveEditorCamera>>();
+    let (&transform, pan_orbit, orthographic) = active_camera.get_single(world).ok()?;
+
+    let selected = world.resource::<Selection>().selected.clone();
+    let hidden_groups = cx
+        .state::<VisibilityWindow>()
+        .map(|visibility| visibility.hidden_groups().map(str::to_owned).collect())
+        .unwrap_or_default();
+
+    Some(SceneView {
+        name,
+        camera,
+        transform,
+        pan_orbit: pan_orbit.map(|pan_orbit| (pan_orbit.focus, pan_orbit.radius)),
+        orthographic_scale: orthographic.map(|orthographic| orthographic.scale),
+        selected,
+        hidden_groups,
+    })
+}
+
+/// Moves the editor camera to the view and restores its selection and hidden visibility groups.
+/// Entities which no longer exist are dropped from the selection.
+pub fn apply_view(world: &mut World, cx: &mut EditorWindowContext, view: &SceneView) {
+    let state = cx.state_mut::<CameraWindow>().unwrap();
+    CameraWindow::set_active_camera(world, state, view.camera);
+    move_active_camera(
+        world,
+        view.transform,
+        view.pan_orbit,
+        view.orthographic_scale,
+        0.0,
+    );
+
+    let selected = view
+        .selected
+        .iter()
+        .copied()
+        .filter(|&entity| world.get_entity(entity).is_some())
+        .collect();
+    world.resource_mut::<Selection>().selected = selected;
+
+    if let Some(visibility) = cx.state_mut::<VisibilityWindow>() {
+        visibility.show_all(world);
+        for group in &view.hidden_groups {
+            visibility.hide_group(world, group);
+        }
+    }
+}
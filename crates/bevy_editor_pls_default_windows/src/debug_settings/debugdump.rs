@@ -35,18 +35,18 @@ pub fn setup(app: &mut App) {
     let rendergraph_settings = render_graph::settings::Settings::default();
 
     let update_schedule = app.get_schedule(Update).map(|schedule| {
-        schedule_graph::schedule_graph_dot(schedule, &app.world(), &schedule_settings)
+        schedule_graph::schedule_graph_dot(schedule, app.world(), &schedule_settings)
     });
 
     let fixed_update_schedule = app.get_schedule(FixedUpdate).map(|schedule| {
-        schedule_graph::schedule_graph_dot(schedule, &app.world(), &schedule_settings)
+        schedule_graph::schedule_graph_dot(schedule, app.world(), &schedule_settings)
     });
 
     let render_main_schedule = render_app.get_schedule(Render).map(|schedule| {
-        schedule_graph::schedule_graph_dot(schedule, &app.world(), &schedule_settings)
+        schedule_graph::schedule_graph_dot(schedule, app.world(), &schedule_settings)
     });
     let render_extract_schedule = render_app.get_schedule(ExtractSchedule).map(|schedule| {
-        schedule_graph::schedule_graph_dot(schedule, &app.world(), &schedule_settings)
+        schedule_graph::schedule_graph_dot(schedule, app.world(), &schedule_settings)
     });
 
     let render_graph = render_graph::render_graph_dot(render_graph, &rendergraph_settings);
@@ -71,10 +71,9 @@ pub fn execute_dot(dot: &str, format: &str) -> Result<Vec<u8>, std::io::Error> {
 
     let output = child.wait_with_output()?;
     if !output.status.success() {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            String::from_utf8_lossy(&output.stderr),
-        ));
+        return Err(std::io::Error::other(String::from_utf8_lossy(
+            &output.stderr,
+        )));
     }
 
     Ok(output.stdout)
@@ -1,21 +1,52 @@
 pub mod debugdump;
 
+use std::ops::RangeInclusive;
+
 use bevy::{
+    gizmos::{aabb::AabbGizmoConfigGroup, config::GizmoConfigStore},
     pbr::wireframe::WireframeConfig,
     prelude::*,
     reflect::TypeRegistry,
     render::{render_resource::WgpuFeatures, renderer::RenderAdapter},
 };
-use bevy_editor_pls_core::editor_window::EditorWindow;
+use bevy_editor_pls_core::{
+    editor::{MAX_UI_SCALE, MIN_UI_SCALE},
+    editor_window::EditorWindow,
+    Editor, EditorConfig, EditorEvent,
+};
 use bevy_inspector_egui::{
     egui::{self, Grid},
     reflect_inspector::ui_for_value,
 };
+use serde::{Deserialize, Serialize};
+
+use crate::inspector::InspectorDragSpeed;
+
+/// Drag speed picked when enabling a custom inspector drag speed
+const DEFAULT_INSPECTOR_DRAG_SPEED: f32 = 0.1;
+
+/// Outline color of selected entities until it is changed in the debug settings
+const DEFAULT_OUTLINE_COLOR: Color = Color::srgb(1.0, 0.6, 0.1);
+
+/// Range of the editor UI scale slider. Ctrl+scroll over the menu bar goes beyond it, up to the
+/// editor's limits, and the slider keeps such values.
+const UI_SCALE_SLIDER_RANGE: RangeInclusive<f32> = 0.75..=2.0;
 
 pub struct DebugSettingsWindowState {
     pub pause_time: bool,
     pub wireframes: bool,
     pub highlight_selected: bool,
+    /// Outlines the selected meshes, and circles selected entities without a mesh
+    pub outline_selected: bool,
+    pub outline_color: Color,
+    /// Draws the bounding boxes of the selected entities
+    pub show_selection_bounds: bool,
+    /// Draws the local X/Y/Z axes of the selected entities
+    pub show_local_axes: bool,
+    /// Pause the game's virtual time while the editor is active, resuming it once the editor is closed
+    pub freeze_while_editor_active: bool,
+    /// Folder viewport screenshots are saved to, the working directory if empty
+    pub screenshot_folder: String,
 
     open_debugdump_status: Option<DebugdumpError>,
 }
@@ -33,6 +64,12 @@ impl Default for DebugSettingsWindowState {
             pause_time: false,
             wireframes: false,
             highlight_selected: true,
+            outline_selected: true,
+            outline_color: DEFAULT_OUTLINE_COLOR,
+            show_selection_bounds: false,
+            show_local_axes: true,
+            freeze_while_editor_active: false,
+            screenshot_folder: String::new(),
 
             open_debugdump_status: None,
         }
@@ -43,6 +80,8 @@ pub struct DebugSettingsWindow;
 impl EditorWindow for DebugSettingsWindow {
     type State = DebugSettingsWindowState;
     const NAME: &'static str = "Debug settings";
+    const CATEGORY: &'static str = "Debug";
+    const ICON: Option<&'static str> = Some("⚙");
 
     fn ui(
         world: &mut bevy::prelude::World,
@@ -56,11 +95,91 @@ impl EditorWindow for DebugSettingsWindow {
         debug_ui(world, state, ui, &type_registry);
     }
 
+    fn save_state(state: &Self::State) -> Option<String> {
+        ron::to_string(&SavedDebugSettings {
+            highlight_selected: state.highlight_selected,
+            outline_selected: state.outline_selected,
+            outline_color: state.outline_color.to_srgba().to_f32_array(),
+            show_selection_bounds: state.show_selection_bounds,
+            show_local_axes: state.show_local_axes,
+            freeze_while_editor_active: state.freeze_while_editor_active,
+            screenshot_folder: state.screenshot_folder.clone(),
+        })
+        .ok()
+    }
+
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Ok(saved) = ron::from_str::<SavedDebugSettings>(saved) {
+            state.highlight_selected = saved.highlight_selected;
+            state.outline_selected = saved.outline_selected;
+            state.outline_color = Srgba::from_f32_array(saved.outline_color).into();
+            state.show_selection_bounds = saved.show_selection_bounds;
+            state.show_local_axes = saved.show_local_axes;
+            state.freeze_while_editor_active = saved.freeze_while_editor_active;
+            state.screenshot_folder = saved.screenshot_folder;
+        }
+    }
+
+    fn app_setup(app: &mut App) {
+        app.add_systems(Update, freeze_time_while_editor_active);
+    }
+
     fn app_finish(app: &mut App) {
         debugdump::setup(app);
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct SavedDebugSettings {
+    highlight_selected: bool,
+    #[serde(default = "default_outline_selected")]
+    outline_selected: bool,
+    #[serde(default = "default_outline_color")]
+    outline_color: [f32; 4],
+    #[serde(default)]
+    show_selection_bounds: bool,
+    show_local_axes: bool,
+    #[serde(default)]
+    freeze_while_editor_active: bool,
+    #[serde(default)]
+    screenshot_folder: String,
+}
+
+fn default_outline_selected() -> bool {
+    true
+}
+
+fn default_outline_color() -> [f32; 4] {
+    DEFAULT_OUTLINE_COLOR.to_srgba().to_f32_array()
+}
+
+/// Pauses time when the editor is opened with [`DebugSettingsWindowState::freeze_while_editor_active`],
+/// and unpauses it again when the editor is closed, unless it was already paused before.
+fn freeze_time_while_editor_active(
+    mut editor_events: EventReader<EditorEvent>,
+    editor: Res<Editor>,
+    mut time: ResMut<Time<Virtual>>,
+    mut frozen_by_editor: Local<bool>,
+) {
+    let freeze = editor
+        .window_state::<DebugSettingsWindow>()
+        .is_some_and(|state| state.freeze_while_editor_active);
+
+    for event in editor_events.read() {
+        let EditorEvent::Toggle { now_active } = *event else {
+            continue;
+        };
+
+        if now_active && freeze && !time.is_paused() {
+            time.pause();
+            *frozen_by_editor = true;
+        } else if !now_active && *frozen_by_editor {
+            time.unpause();
+            *frozen_by_editor = false;
+        }
+    }
+}
+
 fn debug_ui(
     world: &mut World,
     state: &mut DebugSettingsWindowState,
@@ -104,6 +223,8 @@ fn debug_ui_options(
 
         let mut time = world.resource_mut::<Time<Virtual>>();
 
+        // the game can also be paused from the menu bar or with a hotkey
+        state.pause_time = time.is_paused();
         if ui.checkbox(&mut state.pause_time, "").changed() {
             if state.pause_time {
                 time.pause();
@@ -112,15 +233,15 @@ fn debug_ui_options(
             }
         }
         ui.end_row();
+        ui.label("Freeze game while editor is active");
+        ui.checkbox(&mut state.freeze_while_editor_active, "");
+        ui.end_row();
+
         ui.label("Game Speed");
 
         let mut speed = time.relative_speed_f64();
         if ui
-            .add(
-                egui::DragValue::new(&mut speed)
-                    .range(0..=20)
-                    .speed(0.1),
-            )
+            .add(egui::DragValue::new(&mut speed).range(0..=20).speed(0.1))
             .changed()
         {
             time.set_relative_speed_f64(speed);
@@ -129,7 +250,7 @@ fn debug_ui_options(
 
         let wireframe_enabled = world
             .get_resource::<RenderAdapter>()
-            .map_or(false, |adapter| {
+            .is_some_and(|adapter| {
                 adapter
                     .0
                     .features()
@@ -159,6 +280,86 @@ fn debug_ui_options(
             ui.checkbox(&mut state.highlight_selected, "");
         });
         ui.end_row();
+
+        ui.label("Outline selected entities");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut state.outline_selected, "");
+            ui.add_enabled_ui(state.outline_selected, |ui| {
+                let mut rgba = state.outline_color.to_srgba().to_f32_array();
+                if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                    state.outline_color = Srgba::from_f32_array(rgba).into();
+                }
+            });
+        });
+        ui.end_row();
+
+        ui.label("Show local axes of selected entity");
+        ui.checkbox(&mut state.show_local_axes, "");
+        ui.end_row();
+
+        ui.label("Show selection bounds");
+        ui.checkbox(&mut state.show_selection_bounds, "");
+        ui.end_row();
+
+        if let Some(mut config_store) = world.get_resource_mut::<GizmoConfigStore>() {
+            ui.label("Show all bounds");
+            let (_, aabb_config) = config_store.config_mut::<AabbGizmoConfigGroup>();
+            ui.checkbox(&mut aabb_config.draw_all, "");
+            ui.end_row();
+        }
+
+        ui.label("Opaque editor background");
+        let mut editor_config = world.resource_mut::<EditorConfig>();
+        ui.checkbox(&mut editor_config.opaque_background, "");
+        ui.end_row();
+
+        ui.label("Editor UI scale");
+        let mut ui_scale = editor_config.ui_scale.unwrap_or(1.0);
+        let slider = egui::Slider::new(&mut ui_scale, UI_SCALE_SLIDER_RANGE)
+            .clamp_to_range(false)
+            .step_by(0.05)
+            .max_decimals(2);
+        let response = ui
+            .add(slider)
+            .on_hover_text("Scale of the editor text and widgets, saved for the next session");
+        if response.changed() {
+            editor_config.ui_scale = Some(ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE));
+        }
+        ui.end_row();
+
+        ui.label("Screenshot folder");
+        ui.add(
+            egui::TextEdit::singleline(&mut state.screenshot_folder)
+                .hint_text("Working directory")
+                .desired_width(120.0),
+        );
+        ui.end_row();
+
+        ui.label("Inspector drag speed");
+        let mut drag_speed = world.resource::<InspectorDragSpeed>().default;
+        let changed = ui
+            .horizontal(|ui| {
+                let mut custom = drag_speed.is_some();
+                let mut changed = ui
+                    .checkbox(&mut custom, "")
+                    .on_hover_text("Use a custom drag speed for float fields in the inspector")
+                    .changed();
+                if changed {
+                    drag_speed = custom.then_some(DEFAULT_INSPECTOR_DRAG_SPEED);
+                }
+                if let Some(speed) = &mut drag_speed {
+                    let drag = egui::DragValue::new(speed)
+                        .range(0.0..=f32::MAX)
+                        .speed(0.001);
+                    changed |= ui.add(drag).changed();
+                }
+                changed
+            })
+            .inner;
+        if changed {
+            world.resource_mut::<InspectorDragSpeed>().default = drag_speed;
+        }
+        ui.end_row();
     });
 }
 
@@ -1,29 +1,334 @@
-use bevy::{diagnostic::DiagnosticsStore, prelude::*};
-use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::{DiagnosticPath, DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    ecs::component::ComponentId,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_editor_pls_core::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    Editor,
+};
 use bevy_inspector_egui::egui;
+use egui_plot::{Line, Plot};
+
+/// How many frames of diagnostics are kept
+const HISTORY_LENGTH: usize = 300;
+
+const DEFAULT_CSV_FILENAME: &str = "diagnostics.csv";
+
+/// How often the entity and component counts are recomputed
+const WORLD_COUNTS_INTERVAL: Duration = Duration::from_secs(1);
+/// How many of the most common component types are listed
+const TOP_COMPONENTS: usize = 10;
+
+/// Entity and component counts of the world, see [`WORLD_COUNTS_INTERVAL`]
+struct WorldCounts {
+    entities: u32,
+    archetypes: usize,
+    /// The [`TOP_COMPONENTS`] component types with the most instances, descending
+    components: Vec<(String, usize)>,
+    /// Real time when the counts were taken
+    updated: Duration,
+}
+
+pub struct DiagnosticsWindowState {
+    /// Show statistics about the editor UI itself, like egui memory usage
+    pub show_editor_stats: bool,
+    /// The diagnostic drawn in the history plot
+    pub plotted: DiagnosticPath,
+    /// Values of all diagnostics over the last [`HISTORY_LENGTH`] frames, oldest first
+    history: VecDeque<HashMap<DiagnosticPath, f64>>,
+    /// Diagnostics left out of the list and the CSV export, so new ones are shown by default
+    pub hidden: HashSet<DiagnosticPath>,
+    csv_filename: String,
+    /// Message about the last CSV export
+    csv_status: Option<Result<String, std::io::Error>>,
+    world_counts: Option<WorldCounts>,
+}
+
+impl Default for DiagnosticsWindowState {
+    fn default() -> Self {
+        DiagnosticsWindowState {
+            show_editor_stats: false,
+            plotted: FrameTimeDiagnosticsPlugin::FPS,
+            history: VecDeque::with_capacity(HISTORY_LENGTH),
+            hidden: HashSet::default(),
+            csv_filename: String::new(),
+            csv_status: None,
+            world_counts: None,
+        }
+    }
+}
 
 pub struct DiagnosticsWindow;
 impl EditorWindow for DiagnosticsWindow {
-    type State = ();
+    type State = DiagnosticsWindowState;
     const NAME: &'static str = "Diagnostics";
+    const CATEGORY: &'static str = "Debug";
+    const ICON: Option<&'static str> = Some("📈");
 
-    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
-        let diagnostics = match world.get_resource::<DiagnosticsStore>() {
-            Some(diagnostics) => diagnostics,
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let state = cx.state_mut::<DiagnosticsWindow>().unwrap();
+        match world.get_resource::<DiagnosticsStore>() {
+            Some(diagnostics) => {
+                diagnostic_ui(ui, diagnostics, &mut state.hidden);
+                ui.separator();
+                history_plot_ui(ui, diagnostics, state);
+                ui.separator();
+                csv_export_ui(ui, state);
+            }
             None => {
                 ui.label("Diagnostics resource not available");
-                return;
             }
-        };
-        diagnostic_ui(ui, diagnostics);
+        }
+
+        ui.separator();
+        world_counts_ui(world, ui, state);
+
+        ui.separator();
+        ui.checkbox(&mut state.show_editor_stats, "Editor stats");
+        if state.show_editor_stats {
+            editor_stats_ui(ui, &cx);
+        }
+    }
+
+    fn app_setup(app: &mut App) {
+        app.add_systems(Update, record_diagnostic_history);
+    }
+
+    fn save_state(state: &Self::State) -> Option<String> {
+        let hidden: Vec<&str> = state.hidden.iter().map(DiagnosticPath::as_str).collect();
+        ron::to_string(&hidden).ok()
+    }
+
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Ok(hidden) = ron::from_str::<Vec<String>>(saved) {
+            state.hidden = hidden.into_iter().map(DiagnosticPath::new).collect();
+        }
+    }
+}
+
+/// Records the diagnostics every frame, also while the window is closed
+fn record_diagnostic_history(
+    mut editor: ResMut<Editor>,
+    diagnostics: Option<Res<DiagnosticsStore>>,
+) {
+    let Some(diagnostics) = diagnostics else {
+        return;
+    };
+    let Some(state) = editor.window_state_mut::<DiagnosticsWindow>() else {
+        return;
+    };
+    let frame = diagnostics
+        .iter()
+        .filter_map(|diagnostic| Some((diagnostic.path().clone(), diagnostic.value()?)))
+        .collect();
+
+    if state.history.len() == HISTORY_LENGTH {
+        state.history.pop_front();
+    }
+    state.history.push_back(frame);
+}
+
+fn history_plot_ui(
+    ui: &mut egui::Ui,
+    diagnostics: &DiagnosticsStore,
+    state: &mut DiagnosticsWindowState,
+) {
+    egui::ComboBox::from_label("Plot")
+        .selected_text(state.plotted.as_str())
+        .show_ui(ui, |ui| {
+            for diagnostic in diagnostics.iter() {
+                let path = diagnostic.path();
+                ui.selectable_value(&mut state.plotted, path.clone(), path.as_str());
+            }
+        });
+
+    let suffix = diagnostics
+        .get(&state.plotted)
+        .map_or("", |diagnostic| diagnostic.suffix.as_ref());
+    let points: Vec<[f64; 2]> = state
+        .history
+        .iter()
+        .enumerate()
+        .filter_map(|(i, frame)| Some([i as f64, *frame.get(&state.plotted)?]))
+        .collect();
+
+    Plot::new("diagnostic history")
+        .height(120.0)
+        .include_x(HISTORY_LENGTH as f64)
+        .include_y(0.0)
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .y_axis_label(suffix)
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(points).fill(0.0).name(state.plotted.as_str()));
+        });
+}
+
+fn world_counts_ui(world: &World, ui: &mut egui::Ui, state: &mut DiagnosticsWindowState) {
+    let now = world.resource::<Time<Real>>().elapsed();
+    let outdated = state
+        .world_counts
+        .as_ref()
+        .is_none_or(|counts| now.saturating_sub(counts.updated) >= WORLD_COUNTS_INTERVAL);
+    if outdated {
+        state.world_counts = Some(count_world(world, now));
+    }
+    let counts = state.world_counts.as_ref().unwrap();
+
+    egui::Grid::new("world counts").show(ui, |ui| {
+        ui.label("Entities");
+        ui.label(counts.entities.to_string());
+        ui.end_row();
+
+        ui.label("Archetypes");
+        ui.label(counts.archetypes.to_string());
+        ui.end_row();
+    });
+
+    egui::CollapsingHeader::new(format!("Top {TOP_COMPONENTS} components")).show(ui, |ui| {
+        egui::Grid::new("component counts").show(ui, |ui| {
+            for (name, count) in &counts.components {
+                ui.label(name);
+                ui.label(count.to_string());
+                ui.end_row();
+            }
+        });
+    });
+}
+
+fn count_world(world: &World, now: Duration) -> WorldCounts {
+    let mut instances: HashMap<ComponentId, usize> = HashMap::default();
+    for archetype in world.archetypes().iter() {
+        for component in archetype.components() {
+            *instances.entry(component).or_default() += archetype.len();
+        }
+    }
+
+    let mut components: Vec<_> = instances
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    components.sort_by(|(_, a), (_, b)| b.cmp(a));
+    components.truncate(TOP_COMPONENTS);
+    let components = components
+        .into_iter()
+        .map(|(component_id, count)| {
+            let name = world.components().get_info(component_id).map_or_else(
+                || format!("{component_id:?}"),
+                |info| pretty_type_name::pretty_type_name_str(info.name()),
+            );
+            (name, count)
+        })
+        .collect();
+
+    WorldCounts {
+        entities: world.entities().len(),
+        archetypes: world.archetypes().len(),
+        components,
+        updated: now,
+    }
+}
+
+fn csv_export_ui(ui: &mut egui::Ui, state: &mut DiagnosticsWindowState) {
+    ui.horizontal(|ui| {
+        let res = egui::TextEdit::singleline(&mut state.csv_filename)
+            .hint_text(DEFAULT_CSV_FILENAME)
+            .desired_width(120.0)
+            .show(ui);
+        if res.response.changed() {
+            state.csv_status = None;
+        }
+
+        let export = ui
+            .button("Export CSV")
+            .on_hover_text("Save the recorded frames of the shown diagnostics");
+        if export.clicked() {
+            let path = match state.csv_filename.as_str() {
+                "" => DEFAULT_CSV_FILENAME,
+                filename => filename,
+            };
+            let result = std::fs::write(path, history_csv(state));
+            state.csv_status = Some(result.map(|()| format!("Exported to {path}")));
+        }
+    });
+
+    match &state.csv_status {
+        Some(Ok(message)) => {
+            ui.label(egui::RichText::new(message).color(egui::Color32::GREEN));
+        }
+        Some(Err(error)) => {
+            ui.label(egui::RichText::new(error.to_string()).color(egui::Color32::RED));
+        }
+        None => {}
+    }
+}
+
+/// One row per recorded frame, with a column for each diagnostic which isn't hidden.
+/// Frames without a value for a diagnostic leave its cell empty.
+fn history_csv(state: &DiagnosticsWindowState) -> String {
+    let mut columns: Vec<&DiagnosticPath> = state
+        .history
+        .iter()
+        .flat_map(|frame| frame.keys())
+        .filter(|path| !state.hidden.contains(*path))
+        .collect();
+    columns.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    columns.dedup();
+
+    let mut csv = String::from("frame");
+    for path in &columns {
+        csv.push(',');
+        csv.push_str(path.as_str());
+    }
+    csv.push('\n');
+
+    for (i, frame) in state.history.iter().enumerate() {
+        csv.push_str(&i.to_string());
+        for path in &columns {
+            csv.push(',');
+            if let Some(value) = frame.get(*path) {
+                csv.push_str(&value.to_string());
+            }
+        }
+        csv.push('\n');
     }
+    csv
 }
 
-fn diagnostic_ui(ui: &mut egui::Ui, diagnostics: &DiagnosticsStore) {
+fn diagnostic_ui(
+    ui: &mut egui::Ui,
+    diagnostics: &DiagnosticsStore,
+    hidden: &mut HashSet<DiagnosticPath>,
+) {
+    ui.menu_button("⚙", |ui| {
+        for diagnostic in diagnostics.iter() {
+            let path = diagnostic.path();
+            let mut shown = !hidden.contains(path);
+            if ui.checkbox(&mut shown, path.as_str()).changed() {
+                if shown {
+                    hidden.remove(path);
+                } else {
+                    hidden.insert(path.clone());
+                }
+            }
+        }
+    })
+    .response
+    .on_hover_text("Choose the diagnostics to show");
+
     egui::Grid::new("frame time diagnostics").show(ui, |ui| {
         let mut has_diagnostics = false;
         for diagnostic in diagnostics.iter() {
             has_diagnostics = true;
+            if hidden.contains(diagnostic.path()) {
+                continue;
+            }
             ui.label(diagnostic.path().as_str());
             if let Some(average) = diagnostic.average() {
                 ui.label(format!("{:.2}", average));
@@ -42,3 +347,40 @@ fn diagnostic_ui(ui: &mut egui::Ui, diagnostics: &DiagnosticsStore) {
         }
     });
 }
+
+fn editor_stats_ui(ui: &mut egui::Ui, cx: &EditorWindowContext) {
+    let ctx = ui.ctx().clone();
+    let internal_state = cx.internal_state();
+
+    let (layers, stored_values) =
+        ctx.memory(|memory| (memory.layer_ids().count(), memory.data.len()));
+    let textures = ctx.tex_manager().read().num_allocated();
+
+    egui::Grid::new("editor stats").show(ui, |ui| {
+        ui.label("Docked windows");
+        ui.label(internal_state.docked_window_count().to_string());
+        ui.end_row();
+
+        ui.label("Floating windows");
+        ui.label(internal_state.floating_window_count().to_string());
+        ui.end_row();
+
+        ui.label("Floating window ids used");
+        ui.label(internal_state.floating_window_ids_used().to_string());
+        ui.end_row();
+
+        ui.label("egui layers");
+        ui.label(layers.to_string());
+        ui.end_row();
+
+        ui.label("egui stored values");
+        ui.label(stored_values.to_string());
+        ui.end_row();
+
+        ui.label("egui textures");
+        ui.label(textures.to_string());
+        ui.end_row();
+    });
+
+    ui.collapsing("egui memory", |ui| ctx.memory_ui(ui));
+}
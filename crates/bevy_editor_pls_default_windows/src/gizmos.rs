@@ -1,17 +1,26 @@
 use bevy::{
+    color::palettes::css::{BLUE, LIME, RED, YELLOW},
     ecs::{query::QueryFilter, system::RunSystemOnce},
     prelude::*,
-    render::view::RenderLayers,
+    render::{primitives::Aabb, view::RenderLayers},
+    transform::TransformSystem,
+    utils::HashMap,
 };
 
-use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    Editor, Selection, UndoStack,
+};
 use bevy_inspector_egui::egui;
+use serde::{Deserialize, Serialize};
 use transform_gizmo_bevy::GizmoTarget;
-use transform_gizmo_bevy::{EnumSet, GizmoMode};
+use transform_gizmo_bevy::{EnumSet, GizmoMode, GizmoOptions, GizmoOrientation};
 
 use crate::{
-    cameras::{EditorCamera, EDITOR_RENDER_LAYER},
-    hierarchy::HierarchyWindow,
+    cameras::{ActiveEditorCamera, CameraWindow, EditorCamKind, EditorCamera, EDITOR_RENDER_LAYER},
+    debug_settings::DebugSettingsWindow,
+    scenes::NotInScene,
+    undo::TransformEdit,
 };
 
 pub struct GizmoState {
@@ -19,6 +28,20 @@ pub struct GizmoState {
     pub camera_gizmo_active: bool,
     /// Synced with the [transform_gizmo_bevy::GizmoOptions] resource
     pub gizmo_modes: EnumSet<GizmoMode>,
+    /// Whether gizmo drags snap to increments. Synced with [transform_gizmo_bevy::GizmoOptions::snapping]
+    pub snapping: bool,
+    /// While this key is held, [GizmoState::snapping] is inverted for the current drag.
+    ///
+    /// If the `enable_snapping` hotkey of [transform_gizmo_bevy::GizmoHotkeys] is configured as well,
+    /// holding that key always enables snapping, regardless of this modifier.
+    pub snap_invert_modifier: Option<KeyCode>,
+    /// Translation increments along the world X, Y and Z axes. An increment of `0.0` doesn't snap that axis.
+    pub snap_translation: Vec3,
+    /// Rotation increment in degrees, around the axis being dragged
+    pub snap_angle_degrees: f32,
+    pub snap_scale: f32,
+    /// Shows the current gizmo mode and orientation in a corner of the viewport
+    pub show_mode_overlay: bool,
 }
 
 impl Default for GizmoState {
@@ -26,30 +49,196 @@ impl Default for GizmoState {
         Self {
             camera_gizmo_active: true,
             gizmo_modes: GizmoMode::all_translate(),
+            snapping: false,
+            snap_invert_modifier: Some(KeyCode::ControlLeft),
+            snap_translation: Vec3::splat(0.5),
+            snap_angle_degrees: 15.0,
+            snap_scale: 0.25,
+            show_mode_overlay: true,
+        }
+    }
+}
+
+/// The grid drawn in the viewport while the editor is active
+#[derive(Resource, Clone)]
+pub struct ViewportGrid {
+    pub enabled: bool,
+    /// Distance between lines close to the grid. Grows in powers of ten as the camera moves away.
+    pub spacing: f32,
+    /// Number of cells along each side of the grid
+    pub cell_count: u32,
+    pub color: Color,
+}
+
+impl Default for ViewportGrid {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            spacing: 1.0,
+            cell_count: 100,
+            color: Color::srgba(0.5, 0.5, 0.5, 0.3),
         }
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct SavedGizmoState {
+    snapping: bool,
+    show_mode_overlay: bool,
+}
+
 pub struct GizmoWindow;
 
 impl EditorWindow for GizmoWindow {
     type State = GizmoState;
 
     const NAME: &'static str = "Gizmos";
+    const CATEGORY: &'static str = "Viewport";
+
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let gizmo_state = cx.state_mut::<GizmoWindow>().unwrap();
+
+        ui.checkbox(&mut gizmo_state.snapping, "Snapping");
+        if let Some(modifier) = gizmo_state.snap_invert_modifier {
+            ui.label(format!("Hold {modifier:?} to temporarily invert snapping"));
+        }
+        egui::Grid::new("gizmo snapping").show(ui, |ui| {
+            ui.label("Translation");
+            ui.horizontal(|ui| {
+                for increment in gizmo_state.snap_translation.as_mut() {
+                    ui.add(
+                        egui::DragValue::new(increment)
+                            .range(0.0..=f32::MAX)
+                            .speed(0.05),
+                    );
+                }
+            });
+            ui.end_row();
+            ui.label("Rotation");
+            ui.add(
+                egui::DragValue::new(&mut gizmo_state.snap_angle_degrees)
+                    .range(0.0..=180.0)
+                    .suffix("°"),
+            );
+            ui.end_row();
+            ui.label("Scale");
+            ui.add(
+                egui::DragValue::new(&mut gizmo_state.snap_scale)
+                    .range(0.0..=f32::MAX)
+                    .speed(0.01),
+            );
+            ui.end_row();
+        });
+        ui.checkbox(&mut gizmo_state.show_mode_overlay, "Show mode in viewport");
+
+        ui.separator();
+        let mut grid = world.resource_mut::<ViewportGrid>();
+        ui.checkbox(&mut grid.enabled, "Grid");
+        ui.horizontal(|ui| {
+            ui.label("Grid spacing");
+            ui.add(
+                egui::DragValue::new(&mut grid.spacing)
+                    .range(0.01..=1000.0)
+                    .speed(0.1),
+            );
+        });
+    }
+
+    fn save_state(state: &Self::State) -> Option<String> {
+        ron::to_string(&SavedGizmoState {
+            snapping: state.snapping,
+            show_mode_overlay: state.show_mode_overlay,
+        })
+        .ok()
+    }
+
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Ok(saved) = ron::from_str::<SavedGizmoState>(saved) {
+            state.snapping = saved.snapping;
+            state.show_mode_overlay = saved.show_mode_overlay;
+        }
+    }
+
+    fn viewport_ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let gizmo_state = cx.state::<GizmoWindow>().unwrap();
+        if !gizmo_state.show_mode_overlay {
+            return;
+        }
+
+        let mut text = if gizmo_state.camera_gizmo_active {
+            let orientation = match world.resource::<GizmoOptions>().gizmo_orientation {
+                GizmoOrientation::Global => "Global",
+                GizmoOrientation::Local => "Local",
+            };
+            format!(
+                "{} — {orientation}",
+                gizmo_mode_name(gizmo_state.gizmo_modes)
+            )
+        } else {
+            "Gizmo disabled".to_owned()
+        };
+        if gizmo_state.snapping {
+            text.push_str(" (snapping)");
+        }
 
-    fn ui(_world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
-        ui.label("Gizmos can currently not be configured");
-        // could definitely change some settings here in the future
+        let painter = ui.painter();
+        let galley = painter.layout_no_wrap(
+            text,
+            egui::FontId::proportional(12.0),
+            ui.visuals().text_color(),
+        );
+        let pos = ui.max_rect().left_bottom() + egui::vec2(8.0, -8.0);
+        let rect = egui::Align2::LEFT_BOTTOM.anchor_size(pos, galley.size());
+        painter.rect_filled(
+            rect.expand(3.0),
+            3.0,
+            ui.visuals().extreme_bg_color.gamma_multiply(0.7),
+        );
+        painter.galley(rect.min, galley, egui::Color32::PLACEHOLDER);
     }
 
     /// Called every frame (hopefully), could this invariant (namely being called every frame) be documented,
     /// ideally in the [EditorWindow] trait?
-    fn viewport_toolbar_ui(world: &mut World, cx: EditorWindowContext, _ui: &mut egui::Ui) {
+    fn viewport_toolbar_ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let gizmo_state = cx.state_mut::<GizmoWindow>().unwrap();
+        if gizmo_state.camera_gizmo_active {
+            for modes in [
+                GizmoMode::all_translate(),
+                GizmoMode::all_rotate(),
+                GizmoMode::all_scale(),
+            ] {
+                let name = gizmo_mode_name(modes);
+                let selected = gizmo_state.gizmo_modes == modes;
+                if ui.selectable_label(selected, name).clicked() {
+                    gizmo_state.gizmo_modes = modes;
+                }
+            }
+        }
+        ui.checkbox(&mut gizmo_state.snapping, "Snap");
+
+        let mut grid = world.resource_mut::<ViewportGrid>();
+        ui.toggle_value(&mut grid.enabled, "Grid");
+
         let gizmo_state = cx.state::<GizmoWindow>().unwrap();
 
+        let invert_snapping = gizmo_state
+            .snap_invert_modifier
+            .is_some_and(|modifier| world.resource::<ButtonInput<KeyCode>>().pressed(modifier));
+
         // syncs the [GizmoOptions] resource with the current state of the gizmo window
-        let mut gizmo_options = world.resource_mut::<transform_gizmo_bevy::GizmoOptions>();
+        let mut gizmo_options = world.resource_mut::<GizmoOptions>();
         gizmo_options.gizmo_modes = gizmo_state.gizmo_modes;
+        gizmo_options.snapping = gizmo_state.snapping != invert_snapping;
+        gizmo_options.snap_angle = gizmo_state.snap_angle_degrees.to_radians();
+        gizmo_options.snap_scale = gizmo_state.snap_scale;
+        // the gizmo only knows a single increment, the per-axis ones are applied by `snap_gizmo_translation`
+        gizmo_options.snap_distance = gizmo_state
+            .snap_translation
+            .to_array()
+            .into_iter()
+            .filter(|&increment| increment > 0.0)
+            .reduce(f32::min)
+            .unwrap_or(0.0);
 
         if gizmo_state.camera_gizmo_active {
             /// Before [hydrate_gizmos] and [deconstruct_gizmos] are run, this system resets the state of all entities that have a [EntityShouldShowGizmo] component.
@@ -69,15 +258,15 @@ impl EditorWindow for GizmoWindow {
                 entities: Query<Entity, (With<EntityShouldShowGizmo>, Without<GizmoTarget>)>,
             ) {
                 for entity in entities.iter() {
-									if let Some(mut entity) = commands.get_entity(entity) {
-                    trace!(
-                        "Hydrating a gizmo on entity {:?} because it is selected",
-                        entity.id()
-                    );
-                    // implicitly assumes it is the only gizmo target in the world,
-                    // otherwise setting [GizmoTarget].is_focussed may be necessary
-                    entity.insert(GizmoTarget::default());
-									}
+                    if let Some(mut entity) = commands.get_entity(entity) {
+                        trace!(
+                            "Hydrating a gizmo on entity {:?} because it is selected",
+                            entity.id()
+                        );
+                        // implicitly assumes it is the only gizmo target in the world,
+                        // otherwise setting [GizmoTarget].is_focussed may be necessary
+                        entity.insert(GizmoTarget::default());
+                    }
                 }
             }
 
@@ -97,22 +286,20 @@ impl EditorWindow for GizmoWindow {
                 }
             }
 
-            if let Some(hierarchy_state) = cx.state::<HierarchyWindow>() {
-                // here should assign the `EntityShouldShowGizmo` component, which is later synced
-                // with the actual gizmo ui system
+            // here should assign the `EntityShouldShowGizmo` component, which is later synced
+            // with the actual gizmo ui system
 
-                world.run_system_once(reset_gizmos_selected_state);
+            world.run_system_once(reset_gizmos_selected_state);
 
-                let selected_entities = hierarchy_state.selected.iter();
-                for entity in selected_entities {
-                    if let Some(mut entity) = world.get_entity_mut(entity) {
-                        entity.insert(EntityShouldShowGizmo);
-                    }
+            let selected_entities = world.resource::<Selection>().selected.clone();
+            for entity in selected_entities {
+                if let Some(mut entity) = world.get_entity_mut(entity) {
+                    entity.insert(EntityShouldShowGizmo);
                 }
-
-                world.run_system_once(hydrate_gizmos);
-                world.run_system_once(deconstruct_gizmos);
             }
+
+            world.run_system_once(hydrate_gizmos);
+            world.run_system_once(deconstruct_gizmos);
         }
     }
 
@@ -140,12 +327,41 @@ impl EditorWindow for GizmoWindow {
             point_light_mesh: sphere.clone(),
             point_light_material: material_light.clone(),
             directional_light_mesh: sphere.clone(),
-            directional_light_material: material_light,
+            directional_light_material: material_light.clone(),
+            spot_light_mesh: sphere.clone(),
+            spot_light_material: material_light,
             camera_mesh: sphere,
             camera_material: material_camera,
         });
 
+        app.init_resource::<ViewportGrid>();
         app.add_systems(PostUpdate, add_gizmo_markers);
+        app.add_systems(
+            PostUpdate,
+            snap_gizmo_translation.before(TransformSystem::TransformPropagate),
+        );
+        app.add_systems(Last, record_gizmo_drags);
+        app.add_systems(
+            PostUpdate,
+            (
+                draw_selected_local_axes,
+                draw_selected_bounds,
+                draw_viewport_grid,
+            )
+                .after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+fn gizmo_mode_name(modes: EnumSet<GizmoMode>) -> &'static str {
+    if modes == GizmoMode::all_translate() {
+        "Translate"
+    } else if modes == GizmoMode::all_rotate() {
+        "Rotate"
+    } else if modes == GizmoMode::all_scale() {
+        "Scale"
+    } else {
+        "Custom"
     }
 }
 
@@ -155,6 +371,8 @@ struct GizmoMarkerConfig {
     point_light_material: Handle<StandardMaterial>,
     directional_light_mesh: Handle<Mesh>,
     directional_light_material: Handle<StandardMaterial>,
+    spot_light_mesh: Handle<Mesh>,
+    spot_light_material: Handle<StandardMaterial>,
     camera_mesh: Handle<Mesh>,
     camera_material: Handle<StandardMaterial>,
 }
@@ -177,6 +395,7 @@ fn add_gizmo_markers(
 
     point_lights: GizmoMarkerQuery<PointLight>,
     directional_lights: GizmoMarkerQuery<DirectionalLight>,
+    spot_lights: GizmoMarkerQuery<SpotLight>,
     cameras: GizmoMarkerQuery<Camera, Without<EditorCamera>>,
 ) {
     fn add<T: Component, F: QueryFilter, B: Bundle>(
@@ -191,7 +410,7 @@ fn add_gizmo_markers(
                 .entity(entity)
                 .insert(HasGizmoMarker)
                 .with_children(|commands| {
-                    commands.spawn((f(), render_layers.clone(), Name::new(name)));
+                    commands.spawn((f(), render_layers.clone(), Name::new(name), NotInScene));
                 });
         }
     }
@@ -213,6 +432,13 @@ fn add_gizmo_markers(
             ..default()
         },
     );
+    add(&mut commands, spot_lights, "SpotLight Gizmo", || {
+        PbrBundle {
+            mesh: gizmo_marker_meshes.spot_light_mesh.clone_weak(),
+            material: gizmo_marker_meshes.spot_light_material.clone_weak(),
+            ..default()
+        }
+    });
 
     let render_layers = RenderLayers::layer(EDITOR_RENDER_LAYER);
     for entity in &cameras {
@@ -233,7 +459,254 @@ fn add_gizmo_markers(
                     },
                     render_layers.clone(),
                     Name::new("Camera Gizmo"),
+                    NotInScene,
                 ));
             });
     }
 }
+
+/// Fraction of the visible viewport height covered by each drawn axis.
+const LOCAL_AXES_SCREEN_FRACTION: f32 = 0.1;
+
+/// Draws the local coordinate axes of every selected entity, independent of the transform gizmo.
+fn draw_selected_local_axes(
+    editor: Res<Editor>,
+    selection: Res<Selection>,
+    mut gizmos: Gizmos,
+    transforms: Query<&GlobalTransform>,
+    active_camera: Query<
+        (
+            &GlobalTransform,
+            Option<&Projection>,
+            Option<&OrthographicProjection>,
+        ),
+        With<ActiveEditorCamera>,
+    >,
+) {
+    if !editor.active() {
+        return;
+    }
+
+    let show_local_axes = editor
+        .window_state::<DebugSettingsWindow>()
+        .is_some_and(|settings| settings.show_local_axes);
+    if !show_local_axes {
+        return;
+    }
+
+    let Ok((camera_transform, projection, orthographic)) = active_camera.get_single() else {
+        return;
+    };
+
+    for entity in selection.iter() {
+        let Ok(transform) = transforms.get(entity) else {
+            continue;
+        };
+
+        let (_, rotation, origin) = transform.to_scale_rotation_translation();
+
+        // keep the axes at a constant size on screen
+        let visible_height = visible_height_at(camera_transform, projection, orthographic, origin);
+        let length = visible_height * LOCAL_AXES_SCREEN_FRACTION;
+
+        gizmos.arrow(origin, origin + rotation * Vec3::X * length, RED);
+        gizmos.arrow(origin, origin + rotation * Vec3::Y * length, LIME);
+        gizmos.arrow(origin, origin + rotation * Vec3::Z * length, BLUE);
+    }
+}
+
+/// Draws the bounding box of every selected entity with a mesh, if enabled in the debug settings.
+/// The bounds of all entities are drawn by bevy's [`AabbGizmoConfigGroup`] instead.
+fn draw_selected_bounds(
+    editor: Res<Editor>,
+    selection: Res<Selection>,
+    mut gizmos: Gizmos,
+    bounds: Query<(&Aabb, &GlobalTransform)>,
+) {
+    let show_selection_bounds = editor
+        .window_state::<DebugSettingsWindow>()
+        .is_some_and(|settings| settings.show_selection_bounds);
+    if !editor.active() || !show_selection_bounds {
+        return;
+    }
+
+    for entity in selection.iter() {
+        let Ok((aabb, transform)) = bounds.get(entity) else {
+            continue;
+        };
+        let aabb_transform = Transform::from_translation(aabb.center.into())
+            .with_scale((aabb.half_extents * 2.0).into());
+        gizmos.cuboid(*transform * aabb_transform, YELLOW);
+    }
+}
+
+/// Height of the area the camera sees at the distance of `point`, for drawing things at a constant size on screen
+pub(crate) fn visible_height_at(
+    camera_transform: &GlobalTransform,
+    projection: Option<&Projection>,
+    orthographic: Option<&OrthographicProjection>,
+    point: Vec3,
+) -> f32 {
+    match (projection, orthographic) {
+        (Some(Projection::Perspective(perspective)), _) => {
+            let distance = camera_transform.translation().distance(point);
+            2.0 * distance * (perspective.fov * 0.5).tan()
+        }
+        (Some(Projection::Orthographic(orthographic)), _) | (None, Some(orthographic)) => {
+            orthographic.area.height()
+        }
+        (None, None) => 1.0,
+    }
+}
+
+/// Draws the [`ViewportGrid`] on the ground plane, or on the `z = 0` plane for the 2d editor camera.
+fn draw_viewport_grid(
+    editor: Res<Editor>,
+    grid: Res<ViewportGrid>,
+    mut gizmos: Gizmos,
+    active_camera: Query<
+        (&GlobalTransform, Option<&OrthographicProjection>),
+        With<ActiveEditorCamera>,
+    >,
+) {
+    if !editor.active() || !grid.enabled || grid.spacing <= 0.0 {
+        return;
+    }
+    let Ok((camera_transform, orthographic)) = active_camera.get_single() else {
+        return;
+    };
+
+    let d2 = editor
+        .window_state::<CameraWindow>()
+        .is_some_and(|state| state.editor_cam() == EditorCamKind::D2PanZoom);
+    let camera = camera_transform.translation();
+    let (rotation, distance, center) = match d2 {
+        true => (
+            Quat::IDENTITY,
+            orthographic.map_or(1.0, |orthographic| orthographic.area.height()),
+            camera.truncate(),
+        ),
+        false => (
+            Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+            camera.y.abs(),
+            camera.xz(),
+        ),
+    };
+
+    // coarser lines the further away the camera is, so the grid doesn't turn into noise
+    let zoom_steps = (distance / (grid.spacing * 10.0)).max(1.0).log10().floor();
+    let spacing = grid.spacing * 10f32.powf(zoom_steps);
+    // keep the lines in place while the camera moves
+    let center = (center / spacing).round() * spacing;
+    let position = match d2 {
+        true => center.extend(0.0),
+        false => Vec3::new(center.x, 0.0, center.y),
+    };
+
+    gizmos.grid(
+        position,
+        rotation,
+        UVec2::splat(grid.cell_count),
+        Vec2::splat(spacing),
+        grid.color,
+    );
+}
+
+/// Translation of an entity while its gizmo is being dragged with snapping
+struct SnappedDrag {
+    start: Vec3,
+    /// Where the gizmo would have moved the entity without the per-axis snapping
+    unsnapped: Vec3,
+    snapped: Vec3,
+}
+
+/// Rounds the translation of dragged entities to the per-axis [`GizmoState::snap_translation`] increments
+fn snap_gizmo_translation(
+    editor: Res<Editor>,
+    gizmo_options: Res<GizmoOptions>,
+    mut targets: Query<(Entity, &GizmoTarget, &mut Transform)>,
+    mut drags: Local<HashMap<Entity, SnappedDrag>>,
+) {
+    let Some(gizmo_state) = editor.window_state::<GizmoWindow>() else {
+        return;
+    };
+    let increments = gizmo_state.snap_translation;
+
+    if !gizmo_options.snapping {
+        drags.clear();
+        return;
+    }
+
+    drags.retain(|&entity, _| {
+        targets
+            .get(entity)
+            .is_ok_and(|(_, target, _)| target.is_active())
+    });
+
+    for (entity, target, mut transform) in &mut targets {
+        if !target.is_active() {
+            continue;
+        }
+
+        let translation = transform.translation;
+        let drag = drags.entry(entity).or_insert(SnappedDrag {
+            start: translation,
+            unsnapped: translation,
+            snapped: translation,
+        });
+        // the gizmo moves the entity relative to the last snapped position
+        drag.unsnapped += translation - drag.snapped;
+
+        let offset = drag.unsnapped - drag.start;
+        let snap = |offset: f32, increment: f32| match increment > 0.0 {
+            true => (offset / increment).round() * increment,
+            false => offset,
+        };
+        drag.snapped = drag.start
+            + Vec3::new(
+                snap(offset.x, increments.x),
+                snap(offset.y, increments.y),
+                snap(offset.z, increments.z),
+            );
+
+        if transform.translation != drag.snapped {
+            transform.translation = drag.snapped;
+        }
+    }
+}
+
+/// Transforms of the gizmo targets from before the current drag
+#[derive(Default)]
+struct TransformsBeforeDrag {
+    dragging: bool,
+    transforms: HashMap<Entity, Transform>,
+}
+
+/// Records finished gizmo drags in the [`UndoStack`]
+fn record_gizmo_drags(world: &mut World, mut before_drag: Local<TransformsBeforeDrag>) {
+    let mut targets = world.query::<(Entity, &GizmoTarget, &Transform)>();
+    if targets.iter(world).any(|(_, target, _)| target.is_active()) {
+        before_drag.dragging = true;
+        return;
+    }
+
+    if std::mem::take(&mut before_drag.dragging) {
+        let edits: Vec<_> = targets
+            .iter(world)
+            .filter_map(|(entity, _, &transform)| {
+                let before = *before_drag.transforms.get(&entity)?;
+                (before != transform).then_some((entity, before, transform))
+            })
+            .collect();
+        if !edits.is_empty() {
+            let edit = TransformEdit::new(world, edits);
+            world.resource_mut::<UndoStack>().push(edit);
+        }
+    }
+
+    before_drag.transforms.clear();
+    let transforms = targets
+        .iter(world)
+        .map(|(entity, _, &transform)| (entity, transform));
+    before_drag.transforms.extend(transforms);
+}
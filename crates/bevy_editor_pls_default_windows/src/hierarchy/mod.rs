@@ -1,33 +1,46 @@
-// pub mod picking;
+mod outline;
+pub mod picking;
+
+use std::any::TypeId;
 
 use bevy::ecs::entity::Entities;
 use bevy::pbr::wireframe::Wireframe;
 use bevy::prelude::*;
 use bevy::reflect::TypeRegistry;
 use bevy::render::{Extract, RenderApp};
+use bevy::utils::HashSet;
 use bevy_inspector_egui::bevy_inspector::guess_entity_name;
-use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
+use bevy_inspector_egui::bevy_inspector::hierarchy::SelectionMode;
 use bevy_inspector_egui::egui::text::CCursorRange;
 use bevy_inspector_egui::egui::{self, ScrollArea};
+use serde::{Deserialize, Serialize};
 
 use bevy_editor_pls_core::{
     editor_window::{EditorWindow, EditorWindowContext},
-    Editor,
+    Editor, Selection,
 };
-// use bevy_mod_picking::backends::egui::EguiPointer;
-// use bevy_mod_picking::prelude::{IsPointerEvent, PointerClick, PointerButton};
 
 use crate::add::{add_ui, AddWindow, AddWindowState};
 use crate::debug_settings::DebugSettingsWindow;
 use crate::inspector::{InspectorSelection, InspectorWindow};
+use crate::notes::EntityNote;
+use crate::reflect_capabilities::ReflectCapabilities;
+use crate::scenes::{spawn_linked_scene_instance, LinkedSceneInstance, NotInScene};
+use crate::undo::{delete_with_undo, record_spawned};
 
 #[derive(Component)]
 pub struct HideInEditor;
 
+/// Marks a [`Visibility`] that was only added by the visibility toggle in the hierarchy,
+/// so it is left out of saved scenes.
+#[derive(Component)]
+pub struct EditorInsertedVisibility;
+
 pub struct HierarchyWindow;
 impl EditorWindow for HierarchyWindow {
     type State = HierarchyState;
     const NAME: &'static str = "Hierarchy";
+    const CATEGORY: &'static str = "Scene";
 
     fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
         let (hierarchy_state, inspector_state, add_state) =
@@ -41,25 +54,80 @@ impl EditorWindow for HierarchyWindow {
                 }
             };
 
-        ScrollArea::vertical().show(ui, |ui| {
-            let type_registry = world.resource::<AppTypeRegistry>().clone();
-            let type_registry = type_registry.read();
-            let new_selected = Hierarchy {
-                world,
-                state: hierarchy_state,
-                type_registry: &type_registry,
-                add_state: add_state.as_deref(),
+        ui.add(
+            egui::TextEdit::singleline(&mut hierarchy_state.filter)
+                .hint_text("Filter by name")
+                .desired_width(f32::INFINITY),
+        );
+
+        ui.horizontal(|ui| {
+            if ui.button("Expand All").clicked() {
+                let mut parents =
+                    world.query_filtered::<Entity, (With<Children>, Without<HideInEditor>)>();
+                hierarchy_state.expanded.extend(parents.iter(world));
             }
-            .show(ui);
-
-            if new_selected {
-                inspector_state.selected = InspectorSelection::Entities;
+            if ui.button("Collapse All").clicked() {
+                hierarchy_state.expanded.clear();
             }
+
+            egui::ComboBox::from_id_source("hierarchy_sort")
+                .selected_text(hierarchy_state.sort.to_string())
+                .show_ui(ui, |ui| {
+                    for &sort in HierarchySort::ALL {
+                        ui.selectable_value(&mut hierarchy_state.sort, sort, sort.to_string());
+                    }
+                });
+        });
+
+        world.resource_scope(|world, mut selection: Mut<Selection>| {
+            ScrollArea::vertical().show(ui, |ui| {
+                let new_selected = Hierarchy {
+                    world,
+                    state: hierarchy_state,
+                    selection: &mut selection,
+                    add_state: add_state.as_deref(),
+                    filtered: None,
+                }
+                .show(ui);
+
+                if new_selected {
+                    inspector_state.selected = InspectorSelection::Entities;
+                }
+            });
         });
     }
 
+    fn save_state(state: &Self::State) -> Option<String> {
+        ron::to_string(&SavedHierarchyState {
+            auto_scroll_to_selected: state.auto_scroll_to_selected,
+            sort: state.sort,
+        })
+        .ok()
+    }
+
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Ok(saved) = ron::from_str::<SavedHierarchyState>(saved) {
+            state.auto_scroll_to_selected = saved.auto_scroll_to_selected;
+            state.sort = saved.sort;
+        }
+    }
+
+    fn viewport_ui(world: &mut World, _: EditorWindowContext, ui: &mut egui::Ui) {
+        let Some(rect) = world.resource::<picking::ViewportBoxSelection>().rect() else {
+            return;
+        };
+        let selection = ui.visuals().selection;
+        ui.painter().rect(
+            rect,
+            0.0,
+            selection.bg_fill.gamma_multiply(0.2),
+            selection.stroke,
+        );
+    }
+
     fn app_setup(app: &mut bevy::prelude::App) {
-        // picking::setup(app);
+        picking::setup(app);
+        outline::setup(app);
         app.add_systems(PostUpdate, clear_removed_entites);
         // .add_system(handle_events);
 
@@ -70,7 +138,7 @@ impl EditorWindow for HierarchyWindow {
 
 fn clear_removed_entites(mut editor: ResMut<Editor>, entities: &Entities) {
     let state = editor.window_state_mut::<HierarchyWindow>().unwrap();
-    state.selected.retain(|entity| entities.contains(entity));
+    state.expanded.retain(|&entity| entities.contains(entity));
 }
 
 /*fn handle_events(
@@ -108,23 +176,89 @@ fn clear_removed_entites(mut editor: ResMut<Editor>, entities: &Entities) {
     }
 }*/
 
-fn extract_wireframe_for_selected(editor: Extract<Res<Editor>>, mut commands: Commands) {
+fn extract_wireframe_for_selected(
+    editor: Extract<Res<Editor>>,
+    selection: Extract<Res<Selection>>,
+    mut commands: Commands,
+) {
     let wireframe_for_selected = editor
         .window_state::<DebugSettingsWindow>()
-        .map_or(false, |settings| settings.highlight_selected);
+        .is_some_and(|settings| settings.highlight_selected);
 
     if wireframe_for_selected {
-        let selected = &editor.window_state::<HierarchyWindow>().unwrap().selected;
-        for selected in selected.iter() {
+        for selected in selection.iter() {
             commands.get_or_spawn(selected).insert(Wireframe);
         }
     }
 }
 
-#[derive(Default)]
+/// Order of the entities within each group of siblings in the hierarchy
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HierarchySort {
+    /// Roots by entity id, children in the order of their parent's [`Children`]
+    #[default]
+    SpawnOrder,
+    NameAscending,
+    NameDescending,
+}
+
+impl HierarchySort {
+    pub const ALL: &'static [HierarchySort] = &[
+        HierarchySort::SpawnOrder,
+        HierarchySort::NameAscending,
+        HierarchySort::NameDescending,
+    ];
+}
+
+impl std::fmt::Display for HierarchySort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HierarchySort::SpawnOrder => write!(f, "Spawn order"),
+            HierarchySort::NameAscending => write!(f, "Name A–Z"),
+            HierarchySort::NameDescending => write!(f, "Name Z–A"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedHierarchyState {
+    auto_scroll_to_selected: bool,
+    #[serde(default)]
+    sort: HierarchySort,
+}
+
 pub struct HierarchyState {
-    pub selected: SelectedEntities,
+    /// Scroll to newly selected entities, e.g. when they were picked in the viewport
+    pub auto_scroll_to_selected: bool,
+    pub sort: HierarchySort,
     rename_info: Option<RenameInfo>,
+    /// Entities whose children are shown
+    expanded: HashSet<Entity>,
+    /// The selection whose ancestors were last expanded
+    revealed: Vec<Entity>,
+    scroll_to: Option<Entity>,
+    /// The entity last clicked without Shift, where Shift+click ranges start
+    selection_anchor: Option<Entity>,
+    /// Only entities whose name contains this (ignoring case) are shown, together with their ancestors
+    pub filter: String,
+    /// The last click was in the hierarchy, so its shortcuts apply
+    focused: bool,
+}
+
+impl Default for HierarchyState {
+    fn default() -> Self {
+        HierarchyState {
+            auto_scroll_to_selected: true,
+            sort: HierarchySort::default(),
+            rename_info: None,
+            expanded: HashSet::default(),
+            revealed: Vec::new(),
+            scroll_to: None,
+            selection_anchor: None,
+            filter: String::new(),
+            focused: false,
+        }
+    }
 }
 
 pub struct RenameInfo {
@@ -133,87 +267,486 @@ pub struct RenameInfo {
     current_rename: String,
 }
 
+impl RenameInfo {
+    /// Starts with the current [`Name`]. Unnamed entities start empty, with their id as placeholder.
+    fn new(world: &World, entity: Entity) -> Self {
+        let current_rename = world
+            .get::<Name>(entity)
+            .map_or_else(String::new, |name| name.as_str().to_owned());
+        RenameInfo {
+            entity,
+            renaming: true,
+            current_rename,
+        }
+    }
+}
+
 struct Hierarchy<'a> {
     world: &'a mut World,
     state: &'a mut HierarchyState,
-    type_registry: &'a TypeRegistry,
+    selection: &'a mut Selection,
     add_state: Option<&'a AddWindowState>,
+    /// Entities matching the filter and their ancestors, `None` if there is no filter
+    filtered: Option<HashSet<Entity>>,
 }
 
 impl<'a> Hierarchy<'a> {
     fn show(&mut self, ui: &mut egui::Ui) -> bool {
-        let mut despawn_recursive = None;
-        let mut despawn = None;
-
-        let HierarchyState {
-            selected,
-            rename_info,
-        } = self.state;
-
-        let new_selection = bevy_inspector_egui::bevy_inspector::hierarchy::Hierarchy {
-            extra_state: rename_info,
-            world: self.world,
-            type_registry: self.type_registry,
-            selected,
-            context_menu: Some(&mut |ui, entity, world, rename_info| {
-                if ui.button("Despawn").clicked() {
-                    despawn_recursive = Some(entity);
-                }
-
-                if ui.button("Remove keeping children").clicked() {
-                    despawn = Some(entity);
-                }
+        self.reveal_new_selection();
+
+        let mut roots: Vec<_> = self
+            .world
+            .query_filtered::<Entity, (Without<Parent>, Without<HideInEditor>)>()
+            .iter(self.world)
+            .collect();
+        roots.sort();
+        self.sort_siblings(&mut roots);
+
+        let filter = self.state.filter.trim().to_lowercase();
+        if !filter.is_empty() {
+            let filtered = self.filtered_entities(&filter);
+            roots.retain(|root| filtered.contains(root));
+            if roots.is_empty() {
+                ui.label("No matching entities");
+            }
+            self.filtered = Some(filtered);
+        }
 
-                if ui.button("Rename").clicked() {
-                    let entity_name = guess_entity_name(world, entity);
-                    *rename_info = Some(RenameInfo {
-                        entity,
-                        renaming: true,
-                        current_rename: entity_name,
-                    });
-                    ui.close_menu();
-                }
+        let mut actions = HierarchyActions::default();
+        let mut new_selection = false;
+        // rows from top to bottom, skipping the children of collapsed entities
+        let mut display_order = Vec::new();
+        for &entity in &roots {
+            self.collect_visible_rows(entity, &mut display_order);
+        }
 
-                if let Some(add_state) = self.add_state {
-                    ui.menu_button("Add", |ui| {
-                        if let Some(add_item) = add_ui(ui, add_state) {
-                            add_item.add_to_entity(world, entity);
-                            ui.close_menu();
-                        }
-                    });
-                }
-            }),
-            shortcircuit_entity: Some(&mut |ui, entity, world, rename_info| {
-                if let Some(rename_info) = rename_info {
-                    if rename_info.renaming && rename_info.entity == entity {
-                        rename_entity_ui(ui, rename_info, world);
+        for &entity in &roots {
+            new_selection |= self.entity_ui(ui, entity, &display_order, &mut actions);
+        }
 
-                        return true;
-                    }
-                }
+        let hierarchy_hovered = ui.rect_contains_pointer(ui.max_rect());
+        if ui.input(|input| input.pointer.any_pressed()) {
+            self.state.focused = hierarchy_hovered;
+        }
 
-                false
-            }),
+        // F2 renames the selected entity inline, like double clicking its row
+        if let &[entity] = self.selection.selected.as_slice() {
+            let already_renaming = self
+                .state
+                .rename_info
+                .as_ref()
+                .is_some_and(|info| info.renaming && info.entity == entity);
+            let f2_pressed = (self.state.focused || hierarchy_hovered)
+                && !ui.ctx().wants_keyboard_input()
+                && ui.input(|input| input.key_pressed(egui::Key::F2));
+
+            if !already_renaming && f2_pressed {
+                self.state.rename_info = Some(RenameInfo::new(self.world, entity));
+            }
         }
-        .show::<Without<HideInEditor>>(ui);
 
-        if let Some(entity) = despawn_recursive {
-            bevy::hierarchy::despawn_with_children_recursive(self.world, entity);
+        if let Some(entity) = actions.despawn_recursive {
+            delete_with_undo(self.world, &[entity]);
+            self.selection.clear();
+        }
+        if let Some(entity) = actions.duplicate {
+            let copy = duplicate_recursive(self.world, entity);
+            record_spawned(self.world, &[copy], "Duplicate");
+            self.selection.select(copy);
+            new_selection = true;
         }
-        if let Some(entity) = despawn {
+        if let Some(entity) = actions.duplicate_linked {
+            if let Some(instance) = spawn_linked_scene_instance(self.world, entity) {
+                self.selection.select(instance);
+                new_selection = true;
+            }
+        }
+        if let Some(entity) = actions.despawn {
             self.world.entity_mut(entity).despawn();
-            self.state.selected.remove(entity);
+            self.selection.remove(entity);
+        }
+
+        new_selection
+    }
+
+    /// Expands the ancestors of newly selected entities, and scrolls to them
+    fn reveal_new_selection(&mut self) {
+        let state = &mut *self.state;
+        if self.selection.selected == state.revealed {
+            return;
         }
+        state.revealed.clone_from(&self.selection.selected);
+
+        for entity in self.selection.iter() {
+            let ancestors = std::iter::successors(self.world.get::<Parent>(entity), |parent| {
+                self.world.get::<Parent>(parent.get())
+            });
+            state.expanded.extend(ancestors.map(Parent::get));
+        }
+
+        if state.auto_scroll_to_selected {
+            state.scroll_to = self.selection.last();
+        }
+    }
+
+    fn filtered_entities(&mut self, filter: &str) -> HashSet<Entity> {
+        let matches: Vec<Entity> = self
+            .world
+            .query::<(Entity, &Name)>()
+            .iter(self.world)
+            .filter(|(_, name)| name.as_str().to_lowercase().contains(filter))
+            .map(|(entity, _)| entity)
+            .collect();
+
+        let mut filtered = HashSet::default();
+        for entity in matches {
+            let ancestors = std::iter::successors(self.world.get::<Parent>(entity), |parent| {
+                self.world.get::<Parent>(parent.get())
+            });
+            filtered.extend(ancestors.map(Parent::get));
+            filtered.insert(entity);
+        }
+        filtered
+    }
+
+    fn is_shown(&self, entity: Entity) -> bool {
+        self.filtered
+            .as_ref()
+            .is_none_or(|filtered| filtered.contains(&entity))
+    }
+
+    /// While filtering, everything leading to a match is expanded
+    fn is_expanded(&self, entity: Entity) -> bool {
+        self.filtered.is_some() || self.state.expanded.contains(&entity)
+    }
+
+    /// The shown children of the entity, in the order of the sort mode
+    fn children(&self, entity: Entity) -> Vec<Entity> {
+        let mut children = self
+            .world
+            .get::<Children>(entity)
+            .map_or_else(Vec::new, |children| {
+                children
+                    .iter()
+                    .copied()
+                    .filter(|&child| self.is_shown(child))
+                    .collect()
+            });
+        self.sort_siblings(&mut children);
+        children
+    }
+
+    /// Sorts by name ignoring case, with unnamed entities after the named ones by their index
+    fn sort_siblings(&self, siblings: &mut [Entity]) {
+        let descending = match self.state.sort {
+            HierarchySort::SpawnOrder => return,
+            HierarchySort::NameAscending => false,
+            HierarchySort::NameDescending => true,
+        };
+
+        siblings.sort_by_cached_key(|&entity| {
+            let name = self.world.get::<Name>(entity);
+            let name = name.map(|name| name.as_str().to_lowercase());
+            (name.is_none(), name, entity.index())
+        });
+        if descending {
+            let named =
+                siblings.partition_point(|&entity| self.world.get::<Name>(entity).is_some());
+            siblings[..named].reverse();
+        }
+    }
 
-        if ui.input(|input| input.key_pressed(egui::Key::Delete)) {
-            for entity in self.state.selected.iter() {
-                self.world.entity_mut(entity).despawn_recursive();
+    /// Expands or collapses the entity together with all of its descendants, used for Alt+click
+    fn set_subtree_expanded(&mut self, entity: Entity, expanded: bool) {
+        let mut stack = vec![entity];
+        while let Some(entity) = stack.pop() {
+            let Some(children) = self.world.get::<Children>(entity) else {
+                continue;
+            };
+            stack.extend(children.iter().copied());
+            if expanded {
+                self.state.expanded.insert(entity);
+            } else {
+                self.state.expanded.remove(&entity);
             }
-            self.state.selected.clear();
+        }
+    }
+
+    fn collect_visible_rows(&self, entity: Entity, rows: &mut Vec<Entity>) {
+        rows.push(entity);
+        if !self.is_expanded(entity) {
+            return;
+        }
+        for child in self.children(entity) {
+            self.collect_visible_rows(child, rows);
+        }
+    }
+
+    fn entity_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        entity: Entity,
+        display_order: &[Entity],
+        actions: &mut HierarchyActions,
+    ) -> bool {
+        if let Some(rename_info) = &mut self.state.rename_info {
+            if rename_info.renaming && rename_info.entity == entity {
+                rename_entity_ui(ui, rename_info, self.world);
+                return false;
+            }
+        }
+
+        let children = self.children(entity);
+        let expanded = !children.is_empty() && self.is_expanded(entity);
+        let selected = self.selection.contains(entity);
+
+        let mut name = guess_entity_name(self.world, entity);
+        if self.world.get::<LinkedSceneInstance>(entity).is_some() {
+            name.push_str(" 🔗");
+        }
+        let mut name = egui::RichText::new(name);
+        if selected {
+            name = name.strong();
+        }
+
+        let visibility = self.world.get::<Visibility>(entity).copied();
+        let hidden = visibility == Some(Visibility::Hidden);
+        let inherited_visible = self
+            .world
+            .get::<InheritedVisibility>(entity)
+            .is_none_or(|inherited| inherited.get());
+        let (eye, eye_hover) = match (hidden, inherited_visible) {
+            (true, _) => (egui::RichText::new("👁").weak().strikethrough(), "Hidden"),
+            (false, false) => (egui::RichText::new("👁").weak(), "Hidden by a parent"),
+            (false, true) => (egui::RichText::new("👁"), "Visible"),
+        };
+
+        let row = ui.horizontal(|ui| {
+            let size = egui::vec2(ui.spacing().icon_width, ui.spacing().interact_size.y);
+            let (_, toggle) = ui.allocate_exact_size(size, egui::Sense::click());
+            if !children.is_empty() {
+                let openness = if expanded { 1.0 } else { 0.0 };
+                egui::collapsing_header::paint_default_icon(ui, openness, &toggle);
+            }
+
+            let eye = ui
+                .add(egui::Button::new(eye).frame(false))
+                .on_hover_text(eye_hover);
+
+            (toggle, eye, ui.selectable_label(selected, name))
+        });
+        let (toggle, eye, mut response) = row.inner;
+
+        if eye.clicked() {
+            let toggled = if hidden {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+            let mut entity_mut = self.world.entity_mut(entity);
+            if visibility.is_none() {
+                entity_mut.insert(EditorInsertedVisibility);
+            }
+            entity_mut.insert(toggled);
+        }
+
+        if toggle.clicked() && !children.is_empty() {
+            let recursive = ui.input(|input| input.modifiers.alt);
+            if recursive {
+                self.set_subtree_expanded(entity, !expanded);
+            } else if expanded {
+                self.state.expanded.remove(&entity);
+            } else {
+                self.state.expanded.insert(entity);
+            }
+        }
+
+        if let Some(note) = self.world.get::<EntityNote>(entity) {
+            response = response.on_hover_text(&note.0);
+        }
+        if self.state.scroll_to == Some(entity) {
+            response.scroll_to_me(None);
+            self.state.scroll_to = None;
+        }
+
+        let mut new_selection = false;
+        if response.clicked() {
+            let selection_mode = ui.input(|input| {
+                SelectionMode::from_ctrl_shift(input.modifiers.ctrl, input.modifiers.shift)
+            });
+            self.click_row(entity, selection_mode, display_order);
+            new_selection = true;
+        }
+        if response.double_clicked() {
+            self.state.rename_info = Some(RenameInfo::new(self.world, entity));
+        }
+
+        response.context_menu(|ui| self.context_menu_ui(ui, entity, actions));
+
+        if expanded {
+            ui.indent(entity, |ui| {
+                for &child in &children {
+                    new_selection |= self.entity_ui(ui, child, display_order, actions);
+                }
+            });
         }
 
         new_selection
     }
+
+    /// Click replaces the selection, Ctrl+click toggles the entity,
+    /// and Shift+click selects the visible rows between the anchor and the entity.
+    fn click_row(&mut self, entity: Entity, mode: SelectionMode, display_order: &[Entity]) {
+        let position = |entity: Entity| display_order.iter().position(|&row| row == entity);
+
+        match mode {
+            SelectionMode::Replace => self.selection.select(entity),
+            SelectionMode::Add => self.selection.toggle(entity),
+            SelectionMode::Extend => {
+                // the anchor may be gone or hidden in a collapsed subtree by now
+                let range = self
+                    .state
+                    .selection_anchor
+                    .and_then(&position)
+                    .zip(position(entity));
+                let Some((from, to)) = range else {
+                    self.selection.select(entity);
+                    self.state.selection_anchor = Some(entity);
+                    return;
+                };
+
+                let (min, max) = if from < to { (from, to) } else { (to, from) };
+                self.selection.clear();
+                for &row in &display_order[min..=max] {
+                    self.selection.add(row);
+                }
+                return;
+            }
+        }
+        self.state.selection_anchor = Some(entity);
+    }
+
+    fn context_menu_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        entity: Entity,
+        actions: &mut HierarchyActions,
+    ) {
+        if let Some(note) = self.world.get::<EntityNote>(entity) {
+            ui.label(egui::RichText::new(&note.0).italics());
+            ui.separator();
+        }
+
+        if ui.button("Delete").clicked() {
+            actions.despawn_recursive = Some(entity);
+        }
+
+        if ui.button("Duplicate").clicked() {
+            actions.duplicate = Some(entity);
+        }
+
+        if ui.button("Remove keeping children").clicked() {
+            actions.despawn = Some(entity);
+        }
+
+        if self.world.get::<Handle<Scene>>(entity).is_some()
+            && ui
+                .button("Duplicate as linked instance")
+                .on_hover_text("Spawns the same scene again, instead of copying the entities")
+                .clicked()
+        {
+            actions.duplicate_linked = Some(entity);
+        }
+
+        if ui.button("Rename").clicked() {
+            self.state.rename_info = Some(RenameInfo::new(self.world, entity));
+            ui.close_menu();
+        }
+
+        if let Some(add_state) = self.add_state {
+            ui.menu_button("Add", |ui| {
+                if let Some(add_item) = add_ui(ui, add_state) {
+                    add_item.add_to_entity(self.world, entity);
+                    ui.close_menu();
+                }
+            });
+        }
+    }
+}
+
+/// Structural changes requested from the context menu, applied after the hierarchy is shown
+#[derive(Default)]
+struct HierarchyActions {
+    despawn_recursive: Option<Entity>,
+    despawn: Option<Entity>,
+    duplicate: Option<Entity>,
+    duplicate_linked: Option<Entity>,
+}
+
+/// How far duplicated entities are moved from the original, so the copy doesn't hide behind it
+const DUPLICATE_OFFSET: Vec3 = Vec3::new(0.5, 0.0, 0.0);
+
+/// Spawns a copy of the entity and its descendants as a sibling, cloning all reflected components.
+///
+/// Components which can't be inserted from a reflected copy are left out, and so are
+/// editor-internal children like gizmo markers, which the editor adds to the copy itself.
+fn duplicate_recursive(world: &mut World, entity: Entity) -> Entity {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let copy = duplicate_entity(world, &type_registry, entity);
+    if let Some(parent) = world.get::<Parent>(entity).map(Parent::get) {
+        world.entity_mut(parent).add_child(copy);
+    }
+    if let Some(mut transform) = world.get_mut::<Transform>(copy) {
+        transform.translation += DUPLICATE_OFFSET;
+    }
+    copy
+}
+
+fn duplicate_entity(world: &mut World, type_registry: &TypeRegistry, entity: Entity) -> Entity {
+    let hierarchy_components = [TypeId::of::<Parent>(), TypeId::of::<Children>()];
+
+    let entity_ref = world.entity(entity);
+    let components: Vec<_> = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+        .filter(|type_id| !hierarchy_components.contains(type_id))
+        .filter_map(|type_id| {
+            let registration = type_registry.get(type_id)?;
+            if !ReflectCapabilities::of(registration).can_insert_reflected() {
+                return None;
+            }
+            let reflect_component = registration.data::<ReflectComponent>()?;
+            let value = reflect_component.reflect(entity_ref)?.clone_value();
+            Some((reflect_component.clone(), value))
+        })
+        .collect();
+
+    let mut copy = world.spawn_empty();
+    for (reflect_component, value) in components {
+        reflect_component.insert(&mut copy, &*value, type_registry);
+    }
+    let copy = copy.id();
+
+    let children: Vec<_> = world
+        .get::<Children>(entity)
+        .into_iter()
+        .flatten()
+        .copied()
+        .filter(|&child| {
+            world.get_entity(child).is_some_and(|child| {
+                !child.contains::<NotInScene>() && !child.contains::<HideInEditor>()
+            })
+        })
+        .collect();
+    for child in children {
+        let child_copy = duplicate_entity(world, type_registry, child);
+        world.entity_mut(copy).add_child(child_copy);
+    }
+
+    copy
 }
 
 fn rename_entity_ui(ui: &mut egui::Ui, rename_info: &mut RenameInfo, world: &mut World) {
@@ -222,22 +755,30 @@ fn rename_entity_ui(ui: &mut egui::Ui, rename_info: &mut RenameInfo, world: &mut
 
     let id = egui::Id::new(rename_info.entity);
 
-    let edit = TextEdit::singleline(&mut rename_info.current_rename).id(id);
+    let edit = TextEdit::singleline(&mut rename_info.current_rename)
+        .id(id)
+        .hint_text(format!("{:?}", rename_info.entity));
     let TextEditOutput {
         response,
         state: mut edit_state,
         ..
     } = edit.show(ui);
 
-    // Runs once to end renaming
+    // Runs once to end renaming, either committing with Enter/focus loss or cancelling with Escape
     if response.lost_focus() {
         rename_info.renaming = false;
 
+        if ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+            return;
+        }
+
         match world.get_entity_mut(rename_info.entity) {
             Some(mut ent_mut) => match ent_mut.get_mut::<Name>() {
                 Some(mut name) => {
                     name.set(rename_info.current_rename.clone());
                 }
+                // leaving the name empty keeps the entity unnamed
+                None if rename_info.current_rename.is_empty() => {}
                 None => {
                     ent_mut.insert(Name::new(rename_info.current_rename.clone()));
                 }
@@ -246,6 +787,7 @@ fn rename_entity_ui(ui: &mut egui::Ui, rename_info: &mut RenameInfo, world: &mut
                 error!("Failed to get renamed entity");
             }
         }
+        return;
     }
 
     // Runs once when renaming begins
@@ -259,3 +801,45 @@ fn rename_entity_ui(ui: &mut egui::Ui, rename_info: &mut RenameInfo, world: &mut
 
     TextEdit::store_state(ui.ctx(), id, edit_state);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Only reflects `Component`, so it can't be inserted from a reflected copy
+    #[derive(Component, Reflect)]
+    #[reflect(Component, from_reflect = false)]
+    struct PartiallyReflected;
+
+    #[test]
+    fn duplicates_leave_out_unconstructible_components_and_editor_children() {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Name>();
+            type_registry.register::<Transform>();
+            type_registry.register::<Parent>();
+            type_registry.register::<Children>();
+            type_registry.register::<PartiallyReflected>();
+        }
+        world.insert_resource(type_registry);
+
+        let entity = world
+            .spawn((Name::new("Light"), Transform::default(), PartiallyReflected))
+            .with_children(|children| {
+                children.spawn((Name::new("Child"), Transform::default()));
+                children.spawn((Name::new("PointLight Gizmo"), NotInScene));
+            })
+            .id();
+
+        let copy = duplicate_recursive(&mut world, entity);
+
+        let copy_ref = world.entity(copy);
+        assert_eq!(copy_ref.get::<Name>().unwrap().as_str(), "Light");
+        assert!(!copy_ref.contains::<PartiallyReflected>());
+        let children = world.get::<Children>(copy).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(world.get::<Name>(children[0]).unwrap().as_str(), "Child");
+    }
+}
@@ -0,0 +1,183 @@
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy::render::render_resource::Face;
+use bevy::render::view::{RenderLayers, VisibilitySystems};
+use bevy::transform::TransformSystem;
+use bevy::utils::HashSet;
+use bevy_editor_pls_core::{Editor, Selection};
+
+use super::{picking::NoEditorPicking, HideInEditor};
+use crate::cameras::{ActiveEditorCamera, EDITOR_RENDER_LAYER};
+use crate::debug_settings::DebugSettingsWindow;
+use crate::gizmos::visible_height_at;
+use crate::scenes::NotInScene;
+
+/// How much larger than the selected mesh its outline is
+const OUTLINE_SCALE: f32 = 1.03;
+/// Radius of the circle around selected entities without a mesh, as a fraction of the visible viewport height
+const MARKER_SCREEN_FRACTION: f32 = 0.03;
+
+/// Enlarged copy of a selected mesh which only shows its back faces, so it appears as an outline around the mesh
+#[derive(Component)]
+struct SelectionOutline {
+    target: Entity,
+}
+
+pub fn setup(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        (
+            sync_selection_outlines
+                .after(TransformSystem::TransformPropagate)
+                .before(VisibilitySystems::CheckVisibility),
+            draw_selected_markers.after(TransformSystem::TransformPropagate),
+        ),
+    );
+}
+
+/// The outline color, if outlines are enabled in the [`DebugSettingsWindow`]
+fn outline_color(editor: &Editor) -> Option<Color> {
+    let settings = editor.window_state::<DebugSettingsWindow>()?;
+    (editor.active() && settings.outline_selected).then_some(settings.outline_color)
+}
+
+/// Spawns a [`SelectionOutline`] for every selected mesh and keeps it in place, despawning it once deselected
+fn sync_selection_outlines(
+    mut commands: Commands,
+    editor: Res<Editor>,
+    selection: Res<Selection>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut material: Local<Option<Handle<StandardMaterial>>>,
+    targets: Query<
+        (
+            &Handle<Mesh>,
+            &GlobalTransform,
+            Option<&Aabb>,
+            &InheritedVisibility,
+        ),
+        Without<SelectionOutline>,
+    >,
+    mut outlines: Query<(
+        Entity,
+        &SelectionOutline,
+        &mut Handle<Mesh>,
+        &mut Transform,
+        &mut GlobalTransform,
+        &mut Visibility,
+    )>,
+) {
+    let color = outline_color(&editor);
+
+    let mut outlined = HashSet::new();
+    for (entity, outline, mut mesh, mut transform, mut global_transform, mut visibility) in
+        &mut outlines
+    {
+        let target = targets
+            .get(outline.target)
+            .ok()
+            .filter(|_| color.is_some() && selection.contains(outline.target));
+        let Some((target_mesh, target_transform, aabb, target_visibility)) = target else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        outlined.insert(outline.target);
+
+        if *mesh != *target_mesh {
+            *mesh = target_mesh.clone();
+        }
+        *global_transform = outline_transform(target_transform, aabb);
+        *transform = global_transform.compute_transform();
+        let target_visibility = match target_visibility.get() {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+        if *visibility != target_visibility {
+            *visibility = target_visibility;
+        }
+    }
+
+    let Some(color) = color else {
+        return;
+    };
+    let material = material.get_or_insert_with(|| {
+        materials.add(StandardMaterial {
+            base_color: color,
+            unlit: true,
+            fog_enabled: false,
+            cull_mode: Some(Face::Front),
+            ..default()
+        })
+    });
+    if materials
+        .get(&*material)
+        .is_some_and(|material| material.base_color != color)
+    {
+        materials.get_mut(&*material).unwrap().base_color = color;
+    }
+
+    for target in selection.iter().filter(|target| !outlined.contains(target)) {
+        let Ok((mesh, target_transform, aabb, _)) = targets.get(target) else {
+            continue;
+        };
+        let global_transform = outline_transform(target_transform, aabb);
+        commands.spawn((
+            SelectionOutline { target },
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: global_transform.compute_transform(),
+                global_transform,
+                ..default()
+            },
+            RenderLayers::layer(EDITOR_RENDER_LAYER),
+            NotShadowCaster,
+            NotShadowReceiver,
+            NotInScene,
+            HideInEditor,
+            NoEditorPicking,
+            Name::new("Selection Outline"),
+        ));
+    }
+}
+
+/// Scales the mesh up around the center of its bounding box
+fn outline_transform(transform: &GlobalTransform, aabb: Option<&Aabb>) -> GlobalTransform {
+    let center = aabb.map_or(Vec3::ZERO, |aabb| Vec3::from(aabb.center));
+    *transform
+        * Transform::from_translation(center).with_scale(Vec3::splat(OUTLINE_SCALE))
+        * Transform::from_translation(-center)
+}
+
+/// Draws a circle facing the camera around selected entities without a mesh, like lights and cameras
+fn draw_selected_markers(
+    editor: Res<Editor>,
+    selection: Res<Selection>,
+    mut gizmos: Gizmos,
+    transforms: Query<&GlobalTransform, Without<Handle<Mesh>>>,
+    active_camera: Query<
+        (
+            &GlobalTransform,
+            Option<&Projection>,
+            Option<&OrthographicProjection>,
+        ),
+        With<ActiveEditorCamera>,
+    >,
+) {
+    let Some(color) = outline_color(&editor) else {
+        return;
+    };
+    let Ok((camera_transform, projection, orthographic)) = active_camera.get_single() else {
+        return;
+    };
+
+    for entity in selection.iter() {
+        let Ok(transform) = transforms.get(entity) else {
+            continue;
+        };
+        let origin = transform.translation();
+        let visible_height = visible_height_at(camera_transform, projection, orthographic, origin);
+        let radius = visible_height * MARKER_SCREEN_FRACTION;
+        gizmos.circle(origin, camera_transform.back(), radius, color);
+    }
+}
@@ -1,32 +1,210 @@
-use bevy::{prelude::*, render::render_resource::PrimitiveTopology};
-use bevy_mod_picking::prelude::{PickRaycastTarget, PickableBundle};
+use bevy::math::bounding::{Aabb3d, RayCast3d};
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy_editor_pls_core::{Editor, Selection};
+use bevy_inspector_egui::egui;
+use transform_gizmo_bevy::GizmoTarget;
 
-pub struct EditorPickingSet;
+use super::HideInEditor;
+use crate::cameras::{camera_2d_panzoom::PanCamControls, ActiveEditorCamera};
+use crate::inspector::{InspectorSelection, InspectorWindow};
+use crate::scenes::NotInScene;
 
-/// Prevents the entity from being selectable in the editor window.
+/// Prevents the entity from being selectable by clicking on it in the viewport.
 #[derive(Component)]
 pub struct NoEditorPicking;
 
+/// Rectangle dragged from empty space in the viewport, selecting the entities inside it on release
+#[derive(Resource, Default)]
+pub struct ViewportBoxSelection {
+    /// Where the drag started and where the pointer is now, in egui points
+    corners: Option<(egui::Pos2, egui::Pos2)>,
+}
+
+impl ViewportBoxSelection {
+    /// The rectangle being dragged, `None` while not box selecting
+    pub fn rect(&self) -> Option<egui::Rect> {
+        self.corners
+            .map(|(start, end)| egui::Rect::from_two_pos(start, end))
+    }
+}
+
+/// Smaller drags count as a click on empty space
+const MIN_BOX_SELECTION_SIZE: f32 = 4.0;
+
 pub fn setup(app: &mut App) {
-    app.add_plugins(bevy_mod_picking::plugins::DefaultPickingPlugins)
-        .add_system(auto_add_editor_picking_set);
+    app.init_resource::<ViewportBoxSelection>().add_systems(
+        PreUpdate,
+        (pick_entity_in_viewport, drag_box_selection).chain(),
+    );
+}
+
+/// Selects the entity whose bounding box is closest to the active editor camera under the cursor
+/// when clicking into the viewport.
+///
+/// Clicking empty space clears the selection, unless Ctrl or Shift is held to add to the selection,
+/// and starts a [`ViewportBoxSelection`]. Clicking editor-internal markers marked [`NotInScene`], like
+/// the gizmo of a light, selects the entity they belong to.
+#[allow(clippy::too_many_arguments)]
+fn pick_entity_in_viewport(
+    mut editor: ResMut<Editor>,
+    mut selection: ResMut<Selection>,
+    mut box_selection: ResMut<ViewportBoxSelection>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&PanCamControls>), With<ActiveEditorCamera>>,
+    gizmo_targets: Query<&GizmoTarget>,
+    pickable: Query<
+        (
+            Entity,
+            &Aabb,
+            &GlobalTransform,
+            &ViewVisibility,
+            Has<NotInScene>,
+            Option<&Parent>,
+        ),
+        (Without<HideInEditor>, Without<NoEditorPicking>),
+    >,
+) {
+    if !editor.active()
+        || !editor.viewport_interaction_active()
+        || !mouse_buttons.just_pressed(MouseButton::Left)
+    {
+        return;
+    }
+    // clicks on the gizmo belong to the gizmo
+    if gizmo_targets.iter().any(|target| target.is_focused()) {
+        return;
+    }
+
+    let Some(cursor_pos) = editor.pointer_state().viewport_pointer_pos else {
+        return;
+    };
+
+    let Ok((camera, camera_transform, pan_cam)) = cameras.get_single() else {
+        return;
+    };
+    let Some(ray) = editor.viewport_ray(cursor_pos, camera, camera_transform) else {
+        return;
+    };
+
+    let picked = pickable
+        .iter()
+        .filter(|(_, _, _, visibility, ..)| visibility.get())
+        .filter_map(|(entity, aabb, transform, _, not_in_scene, parent)| {
+            let entity = if not_in_scene { parent?.get() } else { entity };
+            let distance = ray_aabb_distance(ray, aabb, transform)?;
+            Some((entity, distance))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity);
+
+    let add = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::ShiftLeft,
+        KeyCode::ShiftRight,
+    ]);
+
+    // dragging with the left mouse button pans these cameras instead
+    let left_drag_pans = pan_cam.is_some_and(|controls| {
+        controls.enabled && controls.grab_buttons.contains(&MouseButton::Left)
+    });
+    if picked.is_none() && !left_drag_pans {
+        box_selection.corners = Some((cursor_pos, cursor_pos));
+    }
+
+    match picked {
+        Some(entity) if add => selection.toggle(entity),
+        Some(entity) => selection.select(entity),
+        None if !add => selection.clear(),
+        None => return,
+    }
+
+    if let Some(inspector_state) = editor.window_state_mut::<InspectorWindow>() {
+        inspector_state.selected = InspectorSelection::Entities;
+    }
 }
 
-fn auto_add_editor_picking_set(
-    mut commands: Commands,
-    meshes: Res<Assets<Mesh>>,
-    meshes_query: Query<
-        (Entity, &Handle<Mesh>),
-        (Without<PickRaycastTarget>, Without<NoEditorPicking>),
+/// Follows the pointer while a [`ViewportBoxSelection`] is dragged, and on release selects the entities
+/// whose origin is inside the rectangle. Ctrl or Shift adds them to the selection instead.
+fn drag_box_selection(
+    mut editor: ResMut<Editor>,
+    mut selection: ResMut<Selection>,
+    mut box_selection: ResMut<ViewportBoxSelection>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<ActiveEditorCamera>>,
+    selectable: Query<
+        (Entity, &GlobalTransform, Option<&ViewVisibility>),
+        (
+            Without<HideInEditor>,
+            Without<NoEditorPicking>,
+            Without<NotInScene>,
+        ),
     >,
 ) {
-    for (entity, handle) in meshes_query.iter() {
-        if let Some(mesh) = meshes.get(handle) {
-            if let PrimitiveTopology::TriangleList = mesh.primitive_topology() {
-                commands
-                    .entity(entity)
-                    .insert((PickableBundle::default(), PickRaycastTarget::default()));
-            }
-        }
+    let Some((start, end)) = &mut box_selection.corners else {
+        return;
+    };
+    if let Some(pos) = editor.pointer_state().viewport_pointer_pos {
+        *end = pos;
+    }
+    if !editor.active() {
+        box_selection.corners = None;
+        return;
+    }
+    if mouse_buttons.pressed(MouseButton::Left) {
+        return;
+    }
+
+    let rect = egui::Rect::from_two_pos(*start, *end);
+    box_selection.corners = None;
+    if rect.width().max(rect.height()) < MIN_BOX_SELECTION_SIZE {
+        return;
+    }
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+
+    let inside = selectable
+        .iter()
+        .filter(|(.., visibility)| visibility.is_none_or(|visibility| visibility.get()))
+        .filter(|(_, transform, _)| {
+            camera
+                .world_to_viewport(camera_transform, transform.translation())
+                .is_some_and(|pos| rect.contains(editor.viewport_position_to_egui(pos)))
+        })
+        .map(|(entity, ..)| entity);
+
+    let add = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::ShiftLeft,
+        KeyCode::ShiftRight,
+    ]);
+    if !add {
+        selection.clear();
+    }
+    for entity in inside {
+        selection.add(entity);
+    }
+
+    if let Some(inspector_state) = editor.window_state_mut::<InspectorWindow>() {
+        inspector_state.selected = InspectorSelection::Entities;
     }
 }
+
+/// Distance along the ray to where it enters the bounding box, in world space
+fn ray_aabb_distance(ray: Ray3d, aabb: &Aabb, transform: &GlobalTransform) -> Option<f32> {
+    let world_to_local = transform.affine().inverse();
+    let origin = world_to_local.transform_point3(ray.origin);
+    let direction = Dir3::new(world_to_local.transform_vector3(*ray.direction)).ok()?;
+
+    let bounds = Aabb3d::new(Vec3::from(aabb.center), Vec3::from(aabb.half_extents));
+    let local_distance =
+        RayCast3d::new(origin, direction, f32::MAX).aabb_intersection_at(&bounds)?;
+
+    let hit = transform.transform_point(origin + *direction * local_distance);
+    Some(hit.distance(ray.origin))
+}
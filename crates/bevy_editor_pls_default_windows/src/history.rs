@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::UndoStack;
+use bevy_inspector_egui::egui;
+
+/// Lists the [`EditorCommand`](bevy_editor_pls_core::EditorCommand)s on the [`UndoStack`].
+/// Clicking an entry undoes or redoes commands until the world is back in the state right after it.
+pub struct HistoryWindow;
+
+impl EditorWindow for HistoryWindow {
+    type State = ();
+    const NAME: &'static str = "History";
+    const CATEGORY: &'static str = "Scene";
+    const ICON: Option<&'static str> = Some("🕑");
+
+    fn ui(world: &mut World, _cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let Some(stack) = world.get_resource::<UndoStack>() else {
+            return;
+        };
+        let done: Vec<_> = stack.done().collect();
+        let undone: Vec<_> = stack.undone().collect();
+
+        // the number of commands which should be done after this frame
+        let mut target = None;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if ui
+                .selectable_label(done.is_empty(), "Initial state")
+                .clicked()
+            {
+                target = Some(0);
+            }
+            for (i, description) in done.iter().enumerate() {
+                let current = i + 1 == done.len();
+                if ui.selectable_label(current, description.as_str()).clicked() {
+                    target = Some(i + 1);
+                }
+            }
+            for (i, description) in undone.iter().enumerate() {
+                let label = egui::RichText::new(description).weak();
+                if ui.selectable_label(false, label).clicked() {
+                    target = Some(done.len() + i + 1);
+                }
+            }
+        });
+
+        let Some(target) = target else {
+            return;
+        };
+        let mut steps = done.len();
+        while steps > target && UndoStack::undo(world) {
+            steps -= 1;
+        }
+        while steps < target && UndoStack::redo(world) {
+            steps += 1;
+        }
+    }
+}
@@ -1,13 +1,24 @@
-use std::any::TypeId;
+use std::any::{Any, TypeId};
 
 use super::add::{AddWindow, AddWindowState};
-use super::hierarchy::HierarchyWindow;
+use super::notes::EntityNote;
+use super::reflect_capabilities::{
+    self, ReflectCapabilities, MISSING_DEFAULT, MISSING_FROM_REFLECT,
+};
 use bevy::asset::UntypedAssetId;
-use bevy::prelude::{AppTypeRegistry, Entity, World};
-use bevy::reflect::TypeRegistry;
+use bevy::ecs::component::{ComponentId, Tick};
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::reflect::{TypeRegistration, TypeRegistry};
+use bevy::utils::HashMap;
 use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
-use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
+use bevy_editor_pls_core::{EditorViewportContextMenu, Selection};
+use bevy_inspector_egui::inspector_egui_impls::InspectorEguiImpl;
+use bevy_inspector_egui::inspector_options::std_options::NumberOptions;
+use bevy_inspector_egui::reflect_inspector::{Context, InspectorUi};
+use bevy_inspector_egui::restricted_world_view::RestrictedWorldView;
 use bevy_inspector_egui::{bevy_inspector, egui};
+use indexmap::IndexMap;
 
 #[derive(Eq, PartialEq)]
 pub enum InspectorSelection {
@@ -18,12 +29,26 @@ pub enum InspectorSelection {
 
 pub struct InspectorState {
     pub selected: InspectorSelection,
+    /// Only list components in the change detection section that changed since the last frame
+    pub only_changed_components: bool,
+    last_change_tick: Option<Tick>,
+    /// Filter of the "Add Component" menu
+    add_component_search: String,
+    /// Entities shown instead of the [`Selection`] while the inspector is pinned
+    pub pinned: Option<Vec<Entity>>,
+    /// Name of the pinned entities, shown in the title
+    pinned_label: String,
 }
 
 impl Default for InspectorState {
     fn default() -> Self {
         Self {
             selected: InspectorSelection::Entities,
+            only_changed_components: false,
+            last_change_tick: None,
+            add_component_search: String::new(),
+            pinned: None,
+            pinned_label: String::new(),
         }
     }
 }
@@ -32,46 +57,162 @@ pub struct InspectorWindow;
 impl EditorWindow for InspectorWindow {
     type State = InspectorState;
     const NAME: &'static str = "Inspector";
+    const CATEGORY: &'static str = "Scene";
+    const ICON: Option<&'static str> = Some("🔍");
 
-    fn ui(world: &mut World, cx: EditorWindowContext, ui: &mut egui::Ui) {
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
         let type_registry = world.resource::<AppTypeRegistry>().0.clone();
         let type_registry = type_registry.read();
 
-        let selected = &cx.state::<Self>().unwrap().selected;
-        let selected_entities = &cx.state::<HierarchyWindow>().unwrap().selected;
+        let (inspector_state, add_window_state) =
+            match cx.state_mut_pair::<InspectorWindow, AddWindow>() {
+                Some((a, b)) => (a, Some(b)),
+                None => (cx.state_mut::<InspectorWindow>().unwrap(), None),
+            };
+        let selected = match &mut inspector_state.pinned {
+            Some(pinned) => {
+                pinned.retain(|&entity| world.get_entity(entity).is_some());
+                pinned.clone()
+            }
+            None => world.resource::<Selection>().selected.clone(),
+        };
+
+        if inspector_state.selected == InspectorSelection::Entities {
+            pin_ui(world, inspector_state, &selected, ui);
+        }
 
-        let add_window_state = cx.state::<AddWindow>();
         inspector(
             world,
-            selected,
-            selected_entities,
+            inspector_state,
+            &selected,
             ui,
-            add_window_state,
+            add_window_state.as_deref(),
             &type_registry,
         );
     }
+
+    fn title(state: &Self::State) -> Option<String> {
+        state
+            .pinned
+            .as_ref()
+            .map(|_| format!("{} 🔒 {}", Self::NAME, state.pinned_label))
+    }
+
+    fn save_state(state: &Self::State) -> Option<String> {
+        ron::to_string(&state.only_changed_components).ok()
+    }
+
+    fn load_state(state: &mut Self::State, saved: &str) {
+        if let Ok(only_changed_components) = ron::from_str(saved) {
+            state.only_changed_components = only_changed_components;
+        }
+    }
+
+    fn app_setup(app: &mut App) {
+        app.init_resource::<ComponentClipboard>()
+            .init_resource::<InspectorDragSpeed>()
+            .add_systems(PreUpdate, apply_drag_speed);
+
+        app.world_mut()
+            .get_resource_or_insert_with(EditorViewportContextMenu::default)
+            .add("Paste component", |world, _| {
+                let selected = world.resource::<Selection>().selected.clone();
+                let type_registry = world.resource::<AppTypeRegistry>().clone();
+                let type_registry = type_registry.read();
+                if let Err(reason) = paste_component(world, &selected, &type_registry) {
+                    warn!("Can't paste the component: {reason}");
+                }
+            });
+
+        register_option_handle_ui::<Mesh>(app);
+        register_option_handle_ui::<StandardMaterial>(app);
+        register_option_handle_ui::<Image>(app);
+    }
+}
+
+/// Toggle freezing the inspector on the shown entities, so it doesn't follow the [`Selection`]
+fn pin_ui(world: &World, state: &mut InspectorState, shown: &[Entity], ui: &mut egui::Ui) {
+    if state.pinned.as_ref().is_some_and(Vec::is_empty) {
+        // everything pinned was despawned
+        state.pinned = None;
+    }
+
+    let mut pinned = state.pinned.is_some();
+    let toggle = ui
+        .add_enabled(
+            !shown.is_empty(),
+            egui::SelectableLabel::new(pinned, "🔒 Pin"),
+        )
+        .on_hover_text("Keep inspecting these entities while selecting others");
+    if toggle.clicked() {
+        pinned = !pinned;
+        state.pinned = pinned.then(|| shown.to_vec());
+    }
+
+    if let Some(pinned) = &state.pinned {
+        state.pinned_label = match pinned.as_slice() {
+            &[entity] => world
+                .get::<Name>(entity)
+                .map_or_else(|| format!("{entity:?}"), |name| name.to_string()),
+            entities => format!("{} entities", entities.len()),
+        };
+    }
 }
 
 fn inspector(
     world: &mut World,
-    selected: &InspectorSelection,
-    selected_entities: &SelectedEntities,
+    state: &mut InspectorState,
+    selected_entities: &[Entity],
     ui: &mut egui::Ui,
     add_window_state: Option<&AddWindowState>,
     type_registry: &TypeRegistry,
 ) {
-    egui::ScrollArea::vertical().show(ui, |ui| match *selected {
-        InspectorSelection::Entities => match selected_entities.as_slice() {
+    let this_run = world.read_change_tick();
+    let last_run = state.last_change_tick.replace(this_run).unwrap_or(this_run);
+
+    egui::ScrollArea::vertical().show(ui, |ui| match state.selected {
+        InspectorSelection::Entities => match selected_entities {
             [] => {
                 ui.label("No entity selected");
             }
             &[entity] => {
-                bevy_inspector::ui_for_entity(world, entity, ui);
+                entity_components_ui(world, entity, ui, type_registry);
+                reflect_capabilities::limitations_ui(world, entity, ui, type_registry);
                 add_ui(ui, &[entity], world, add_window_state);
+                add_component_ui(
+                    ui,
+                    &[entity],
+                    world,
+                    &mut state.add_component_search,
+                    type_registry,
+                );
+                reset_component_ui(ui, entity, world, type_registry);
+                component_clipboard_ui(ui, &[entity], world, type_registry);
+                if !world.entity(entity).contains::<EntityNote>() && ui.button("Add note").clicked()
+                {
+                    world.entity_mut(entity).insert(EntityNote::default());
+                }
+                change_detection_ui(
+                    world,
+                    entity,
+                    ui,
+                    &mut state.only_changed_components,
+                    (last_run, this_run),
+                );
             }
             entities => {
+                // edits to shared components are applied to all entities
                 bevy_inspector::ui_for_entities_shared_components(world, entities, ui);
+                unshared_components_ui(world, entities, ui);
                 add_ui(ui, entities, world, add_window_state);
+                add_component_ui(
+                    ui,
+                    entities,
+                    world,
+                    &mut state.add_component_search,
+                    type_registry,
+                );
+                component_clipboard_ui(ui, entities, world, type_registry);
             }
         },
         InspectorSelection::Resource(type_id, ref name) => {
@@ -105,7 +246,906 @@ fn add_ui(
     }
 }
 
+/// Lists the components only some of the `entities` have, which can't be edited together
+fn unshared_components_ui(world: &World, entities: &[Entity], ui: &mut egui::Ui) {
+    let mut counts: IndexMap<ComponentId, usize> = IndexMap::default();
+    for entity_ref in entities
+        .iter()
+        .filter_map(|&entity| world.get_entity(entity))
+    {
+        for component_id in entity_ref.archetype().components() {
+            *counts.entry(component_id).or_default() += 1;
+        }
+    }
+
+    let mut unshared: Vec<_> = counts
+        .into_iter()
+        .filter(|&(_, count)| count < entities.len())
+        .map(|(component_id, count)| {
+            let name = world.components().get_info(component_id).map_or_else(
+                || format!("{component_id:?}"),
+                |info| pretty_type_name::pretty_type_name_str(info.name()),
+            );
+            (name, count)
+        })
+        .collect();
+    if unshared.is_empty() {
+        return;
+    }
+    unshared.sort();
+
+    egui::CollapsingHeader::new(format!("Not shared by all ({})", unshared.len()))
+        .id_source("unshared components")
+        .show(ui, |ui| {
+            egui::Grid::new("unshared components").show(ui, |ui| {
+                for (name, count) in unshared {
+                    ui.label(name);
+                    ui.label(format!("{count}/{}", entities.len()));
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+/// Searchable menu of the registered components which can be constructed through reflection.
+/// Components all `entities` already have are grayed out, others are added to the entities missing them.
+fn add_component_ui(
+    ui: &mut egui::Ui,
+    entities: &[Entity],
+    world: &mut World,
+    search: &mut String,
+    type_registry: &TypeRegistry,
+) {
+    let layout = egui::Layout::top_down(egui::Align::Center).with_cross_justify(true);
+    ui.with_layout(layout, |ui| {
+        ui.menu_button("Add Component", |ui| {
+            ui.add(egui::TextEdit::singleline(search).hint_text("Search"));
+            let search = search.to_lowercase();
+
+            let mut components: Vec<_> = type_registry
+                .iter()
+                .filter(|registration| registration.data::<ReflectComponent>().is_some())
+                .filter(|registration| ReflectCapabilities::of(registration).can_create())
+                .map(|registration| {
+                    let name = registration.type_info().type_path_table().short_path();
+                    (name, registration)
+                })
+                .filter(|(name, _)| name.to_lowercase().contains(&search))
+                .collect();
+            components.sort_by_key(|(name, _)| *name);
+
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (name, registration) in components {
+                        let missing: Vec<Entity> = entities
+                            .iter()
+                            .copied()
+                            .filter(|&entity| !has_component(world, entity, registration.type_id()))
+                            .collect();
+
+                        let button = egui::Button::new(name);
+                        if ui.add_enabled(!missing.is_empty(), button).clicked() {
+                            for entity in missing {
+                                insert_default_component(
+                                    world,
+                                    entity,
+                                    registration,
+                                    type_registry,
+                                );
+                            }
+                            ui.close_menu();
+                        }
+                    }
+                });
+        });
+    });
+}
+
+/// The components of `entity` like [`bevy_inspector::ui_for_entity`], with a button in each header
+/// removing the component
+fn entity_components_ui(
+    world: &mut World,
+    entity: Entity,
+    ui: &mut egui::Ui,
+    type_registry: &TypeRegistry,
+) {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        ui.label(format!("Entity {entity:?} does not exist"));
+        return;
+    };
+    ui.label(bevy_inspector::guess_entity_name(world, entity));
+
+    let mut components: Vec<_> = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| {
+            let info = world.components().get_info(component_id)?;
+            let name = pretty_type_name::pretty_type_name_str(info.name());
+            let removal = info
+                .type_id()
+                .map(|type_id| removal(world, entity, type_id, type_registry));
+            let changed = entity_ref
+                .get_change_ticks_by_id(component_id)
+                .is_some_and(|ticks| {
+                    ticks.is_changed(world.last_change_tick(), world.read_change_tick())
+                });
+            let size = info.layout().size();
+            Some((name, component_id, info.type_id(), size, removal, changed))
+        })
+        .collect();
+    components.sort_by(|(name_a, ..), (name_b, ..)| name_a.cmp(name_b));
+
+    let mut remove = None;
+    let mut queue = CommandQueue::default();
+    let mut world_view = RestrictedWorldView::new(world);
+    for (name, component_id, type_id, size, removal, changed) in components {
+        let id = egui::Id::new(entity).with(component_id);
+        let style = ui.style().clone();
+        if changed && cfg!(feature = "highlight_changes") {
+            highlight_changes(ui);
+        }
+        let header =
+            egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false);
+        let header = header.show_header(ui, |ui| {
+            let label = egui::Label::new(&name)
+                .selectable(false)
+                .sense(egui::Sense::click());
+            let label_clicked = ui.add(label).clicked();
+            if let Some(removal) = removal {
+                let button = egui::Button::new("✖").small().frame(false);
+                let response = ui.add_enabled(removal.is_ok(), button);
+                let response = match removal {
+                    Ok(_) => response.on_hover_text("Remove component"),
+                    Err(reason) => response.on_disabled_hover_text(reason),
+                };
+                if let (true, Ok(reflect_component)) = (response.clicked(), removal) {
+                    remove = Some(reflect_component);
+                }
+            }
+            label_clicked
+        });
+
+        let (_, header_response, _) = header.body(|ui| {
+            ui.set_style(style.clone());
+            let Some(type_id) = type_id else {
+                ui.label(format!("{name} has no type id"));
+                return;
+            };
+            if size == 0 {
+                return;
+            }
+
+            // the context can access the world except for the component shown
+            let (mut component_view, world_view) =
+                world_view.split_off_component((entity, type_id));
+            let mut cx = Context {
+                world: Some(world_view),
+                queue: Some(&mut queue),
+            };
+            let value = component_view.get_entity_component_reflect(entity, type_id, type_registry);
+            match value {
+                Ok((value, _, set_changed)) => {
+                    let changed = InspectorUi::for_bevy(type_registry, &mut cx)
+                        .ui_for_reflect_with_options(value, ui, id.with(component_id), &());
+                    if changed {
+                        set_changed();
+                    }
+                }
+                Err(error) => {
+                    ui.label(format!("Can't show {name}: {error:?}"));
+                }
+            };
+        });
+        ui.set_style(style);
+
+        // clicking the name toggles the component like the arrow does
+        if header_response.inner {
+            if let Some(mut state) = egui::collapsing_header::CollapsingState::load(ui.ctx(), id) {
+                state.toggle(ui);
+                state.store(ui.ctx());
+            }
+        }
+    }
+    queue.apply(world);
+
+    if let Some(reflect_component) = remove {
+        reflect_component.remove(&mut world.entity_mut(entity));
+    }
+}
+
+/// Outlines the following widgets, like `bevy-inspector-egui` does for changed components
+fn highlight_changes(ui: &mut egui::Ui) {
+    let stroke = egui::Stroke::new(1.0, egui::Color32::GOLD);
+    let visuals = &mut ui.style_mut().visuals;
+    visuals.collapsing_header_frame = true;
+    visuals.widgets.inactive.bg_stroke = stroke;
+    visuals.widgets.active.bg_stroke = stroke;
+    visuals.widgets.hovered.bg_stroke = stroke;
+    visuals.widgets.noninteractive.bg_stroke = stroke;
+}
+
+/// How the component can be removed from `entity`, or why it can't be
+fn removal<'a>(
+    world: &World,
+    entity: Entity,
+    type_id: TypeId,
+    type_registry: &'a TypeRegistry,
+) -> Result<&'a ReflectComponent, &'static str> {
+    if let Some(reason) = protected_component_reason(world, entity, type_id) {
+        return Err(reason);
+    }
+    type_registry
+        .get_type_data::<ReflectComponent>(type_id)
+        .ok_or("Not registered with #[reflect(Component)]")
+}
+
+/// Menu resetting one of the reflected components of `entity` to its default value
+fn reset_component_ui(
+    ui: &mut egui::Ui,
+    entity: Entity,
+    world: &mut World,
+    type_registry: &TypeRegistry,
+) {
+    let components = reflected_components(world, entity, type_registry);
+
+    let mut reset = None;
+    let layout = egui::Layout::top_down(egui::Align::Center).with_cross_justify(true);
+    ui.with_layout(layout, |ui| {
+        ui.menu_button("Reset Component", |ui| {
+            let hierarchy = [TypeId::of::<Parent>(), TypeId::of::<Children>()];
+            for &(name, type_id, _) in &components {
+                let Some(registration) = type_registry.get(type_id) else {
+                    continue;
+                };
+                if hierarchy.contains(&type_id) {
+                    continue;
+                }
+                let can_create = ReflectCapabilities::of(registration).can_create();
+                let mut response = ui.add_enabled(can_create, egui::Button::new(name));
+                if !can_create {
+                    response = response.on_disabled_hover_text(MISSING_DEFAULT);
+                }
+                if response.clicked() {
+                    reset = Some(registration);
+                    ui.close_menu();
+                }
+            }
+        });
+    });
+
+    if let Some(registration) = reset {
+        reset_component(world, entity, registration);
+    }
+}
+
+/// The components of the entity registered with [`ReflectComponent`], with their short names, sorted by name
+fn reflected_components<'a>(
+    world: &World,
+    entity: Entity,
+    type_registry: &'a TypeRegistry,
+) -> Vec<(&'a str, TypeId, &'a ReflectComponent)> {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    let mut components: Vec<_> = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+        .filter_map(|type_id| {
+            let registration = type_registry.get(type_id)?;
+            let reflect_component = registration.data::<ReflectComponent>()?;
+            let name = registration.type_info().type_path_table().short_path();
+            Some((name, type_id, reflect_component))
+        })
+        .collect();
+    components.sort_by_key(|(name, ..)| *name);
+    components
+}
+
+/// Component value copied in the inspector, for pasting it onto other entities
+#[derive(Resource, Default)]
+pub struct ComponentClipboard {
+    value: Option<(TypeId, Box<dyn Reflect>)>,
+}
+
+impl ComponentClipboard {
+    pub fn type_id(&self) -> Option<TypeId> {
+        self.value.as_ref().map(|(type_id, _)| *type_id)
+    }
+}
+
+/// Copies a component of a single entity, and pastes the copied component onto all `entities`,
+/// overwriting or inserting it
+fn component_clipboard_ui(
+    ui: &mut egui::Ui,
+    entities: &[Entity],
+    world: &mut World,
+    type_registry: &TypeRegistry,
+) {
+    let layout = egui::Layout::top_down(egui::Align::Center).with_cross_justify(true);
+    ui.with_layout(layout, |ui| {
+        if let &[entity] = entities {
+            let components = reflected_components(world, entity, type_registry);
+            let mut copied = None;
+            ui.menu_button("Copy Component", |ui| {
+                let hierarchy = [TypeId::of::<Parent>(), TypeId::of::<Children>()];
+                for (name, type_id, reflect_component) in components {
+                    if hierarchy.contains(&type_id) || !ui.button(name).clicked() {
+                        continue;
+                    }
+                    copied = reflect_component
+                        .reflect(world.entity(entity))
+                        .map(|value| (type_id, value.clone_value()));
+                    ui.close_menu();
+                }
+            });
+            if let Some(copied) = copied {
+                world.resource_mut::<ComponentClipboard>().value = Some(copied);
+            }
+        }
+
+        let Some(type_id) = world.resource::<ComponentClipboard>().type_id() else {
+            return;
+        };
+        let Some(registration) = type_registry.get(type_id) else {
+            return;
+        };
+        let name = registration.type_info().type_path_table().short_path();
+        let label = match entities.len() {
+            1 => format!("Paste {name}"),
+            len => format!("Paste {name} to all {len} selected"),
+        };
+        let blocked = paste_blocked_reason(world, entities, registration);
+        let mut response = ui.add_enabled(blocked.is_none(), egui::Button::new(label));
+        if let Some(reason) = blocked {
+            response = response.on_disabled_hover_text(reason);
+        }
+        if response.clicked() {
+            let _ = paste_component(world, entities, type_registry);
+        }
+    });
+}
+
+/// Applies the copied component to `entities`, inserting it into the ones missing it
+fn paste_component(
+    world: &mut World,
+    entities: &[Entity],
+    type_registry: &TypeRegistry,
+) -> Result<(), &'static str> {
+    let type_id = world
+        .resource::<ComponentClipboard>()
+        .type_id()
+        .ok_or("No component has been copied")?;
+    let registration = type_registry
+        .get(type_id)
+        .ok_or("The copied component isn't registered")?;
+    if let Some(reason) = paste_blocked_reason(world, entities, registration) {
+        return Err(reason);
+    }
+    let reflect_component = registration
+        .data::<ReflectComponent>()
+        .ok_or("The copied component isn't registered with #[reflect(Component)]")?;
+
+    world.resource_scope(|world, clipboard: Mut<ComponentClipboard>| {
+        let Some((_, value)) = &clipboard.value else {
+            return;
+        };
+        for &entity in entities {
+            if let Some(mut entity) = world.get_entity_mut(entity) {
+                reflect_component.apply_or_insert(&mut entity, &**value, type_registry);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Why the copied component can't be pasted onto all `entities`.
+/// Entities missing it need a concrete value, which has to be built from the reflected copy.
+fn paste_blocked_reason(
+    world: &World,
+    entities: &[Entity],
+    registration: &TypeRegistration,
+) -> Option<&'static str> {
+    let inserts = entities
+        .iter()
+        .any(|&entity| !has_component(world, entity, registration.type_id()));
+    let can_insert = ReflectCapabilities::of(registration).can_insert_reflected();
+    (inserts && !can_insert).then_some(MISSING_FROM_REFLECT)
+}
+
+/// Why the component can't be removed on its own, for components the hierarchy or other components rely on
+fn protected_component_reason(
+    world: &World,
+    entity: Entity,
+    type_id: TypeId,
+) -> Option<&'static str> {
+    let entity = world.get_entity(entity)?;
+    if type_id == TypeId::of::<Parent>() || type_id == TypeId::of::<Children>() {
+        Some("Change the parent in the hierarchy instead")
+    } else if type_id == TypeId::of::<GlobalTransform>() && entity.contains::<Transform>() {
+        Some("Required by Transform")
+    } else if (type_id == TypeId::of::<InheritedVisibility>()
+        || type_id == TypeId::of::<ViewVisibility>())
+        && entity.contains::<Visibility>()
+    {
+        Some("Required by Visibility")
+    } else {
+        None
+    }
+}
+
+fn has_component(world: &World, entity: Entity, type_id: TypeId) -> bool {
+    let Some(component_id) = world.components().get_id(type_id) else {
+        return false;
+    };
+    world
+        .get_entity(entity)
+        .is_some_and(|entity| entity.contains_id(component_id))
+}
+
+fn insert_default_component(
+    world: &mut World,
+    entity: Entity,
+    registration: &TypeRegistration,
+    type_registry: &TypeRegistry,
+) {
+    let Some(value) = reflect_capabilities::default_value(registration, world) else {
+        return;
+    };
+    let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+        return;
+    };
+    if let Some(mut entity) = world.get_entity_mut(entity) {
+        reflect_component.insert(&mut entity, &*value, type_registry);
+    }
+}
+
+/// Overwrites the component of `entity` with a default value, if the type can create one
+fn reset_component(world: &mut World, entity: Entity, registration: &TypeRegistration) {
+    let Some(value) = reflect_capabilities::default_value(registration, world) else {
+        return;
+    };
+    let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+        return;
+    };
+    let Some(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if let Some(mut component) = reflect_component.reflect_mut(&mut entity) {
+        component.apply(&*value);
+    }
+}
+
+/// Lists the components of `entity` with their last change tick, marking those changed since `last_run`
+fn change_detection_ui(
+    world: &World,
+    entity: Entity,
+    ui: &mut egui::Ui,
+    only_changed_components: &mut bool,
+    (last_run, this_run): (Tick, Tick),
+) {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return;
+    };
+
+    egui::CollapsingHeader::new("Change detection")
+        .id_source(("change detection", entity))
+        .show(ui, |ui| {
+            ui.checkbox(only_changed_components, "Only changed components");
+
+            egui::Grid::new("change detection").show(ui, |ui| {
+                for component_id in entity_ref.archetype().components() {
+                    let Some(ticks) = entity_ref.get_change_ticks_by_id(component_id) else {
+                        continue;
+                    };
+                    let changed = ticks.is_changed(last_run, this_run);
+                    if *only_changed_components && !changed {
+                        continue;
+                    }
+
+                    let name = world.components().get_info(component_id).map_or_else(
+                        || format!("{component_id:?}"),
+                        |info| pretty_type_name::pretty_type_name_str(info.name()),
+                    );
+                    ui.label(name);
+                    if changed {
+                        ui.label(egui::RichText::new("changed").color(egui::Color32::YELLOW));
+                    } else {
+                        ui.label("");
+                    }
+                    ui.label(format!("tick {}", ticks.last_changed_tick().get()));
+                    ui.end_row();
+                }
+            });
+        });
+}
+
 pub fn label_button(ui: &mut egui::Ui, text: &str, text_color: egui::Color32) -> bool {
     ui.add(egui::Button::new(egui::RichText::new(text).color(text_color)).frame(false))
         .clicked()
 }
+
+/// Drag speed of float fields in the inspector which don't set their own speed with `InspectorOptions`.
+///
+/// Insert your own to configure it, or change the default speed in the debug settings.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct InspectorDragSpeed {
+    /// Speed of all float fields, `None` keeps the speed picked by egui
+    pub default: Option<f32>,
+    /// Speed by field type (`f32` or `f64`), taking precedence over `default`
+    pub overrides: HashMap<TypeId, f32>,
+}
+
+impl InspectorDragSpeed {
+    pub fn speed(&self, field_type: TypeId) -> Option<f32> {
+        self.overrides.get(&field_type).copied().or(self.default)
+    }
+}
+
+/// Replaces the inspector UI of float types with a configured speed,
+/// restoring the `bevy_inspector_egui` UI once they aren't configured anymore.
+fn apply_drag_speed(
+    drag_speed: Res<InspectorDragSpeed>,
+    type_registry: Res<AppTypeRegistry>,
+    mut original_impls: Local<HashMap<TypeId, InspectorEguiImpl>>,
+) {
+    if !drag_speed.is_changed() {
+        return;
+    }
+
+    let float_impls = [
+        (TypeId::of::<f32>(), float_drag_ui_impl::<f32>()),
+        (TypeId::of::<f64>(), float_drag_ui_impl::<f64>()),
+    ];
+    let mut type_registry = type_registry.write();
+    for (type_id, float_impl) in float_impls {
+        let Some(registration) = type_registry.get_mut(type_id) else {
+            continue;
+        };
+        if drag_speed.speed(type_id).is_some() {
+            if let Some(original) = registration.data::<InspectorEguiImpl>() {
+                original_impls
+                    .entry(type_id)
+                    .or_insert_with(|| original.clone());
+            }
+            registration.insert(float_impl);
+        } else if let Some(original) = original_impls.remove(&type_id) {
+            registration.insert(original);
+        }
+    }
+}
+
+fn float_drag_ui_impl<T: egui::emath::Numeric + Reflect>() -> InspectorEguiImpl {
+    InspectorEguiImpl::new(float_ui::<T>, float_ui_readonly::<T>, float_ui_many::<T>)
+}
+
+fn float_drag_value<'a, T: egui::emath::Numeric>(
+    value: &'a mut T,
+    options: &dyn Any,
+    env: &mut InspectorUi<'_, '_>,
+) -> egui::DragValue<'a> {
+    let options = options.downcast_ref::<NumberOptions<T>>();
+
+    let configured_speed = env
+        .context
+        .world
+        .as_mut()
+        .and_then(|world| world.get_resource_mut::<InspectorDragSpeed>().ok())
+        .and_then(|drag_speed| drag_speed.speed(TypeId::of::<T>()));
+    let speed = options
+        .map(|options| options.speed)
+        .filter(|&speed| speed != 0.0)
+        .or(configured_speed);
+
+    let mut drag_value = egui::DragValue::new(value);
+    if let Some(speed) = speed {
+        drag_value = drag_value.speed(speed);
+    }
+    if let Some(options) = options {
+        let min = options.min.unwrap_or(T::MIN);
+        let max = options.max.unwrap_or(T::MAX);
+        drag_value = drag_value
+            .range(min..=max)
+            .prefix(options.prefix.as_str())
+            .suffix(options.suffix.as_str());
+    }
+    drag_value
+}
+
+fn float_ui<T: egui::emath::Numeric>(
+    value: &mut dyn Any,
+    ui: &mut egui::Ui,
+    options: &dyn Any,
+    _id: egui::Id,
+    mut env: InspectorUi<'_, '_>,
+) -> bool {
+    let value = value.downcast_mut::<T>().unwrap();
+    ui.add(float_drag_value(value, options, &mut env)).changed()
+}
+
+fn float_ui_readonly<T: egui::emath::Numeric>(
+    value: &dyn Any,
+    ui: &mut egui::Ui,
+    options: &dyn Any,
+    _id: egui::Id,
+    mut env: InspectorUi<'_, '_>,
+) {
+    let mut value = *value.downcast_ref::<T>().unwrap();
+    ui.add_enabled(false, float_drag_value(&mut value, options, &mut env));
+}
+
+fn float_ui_many<T: egui::emath::Numeric + Reflect>(
+    ui: &mut egui::Ui,
+    options: &dyn Any,
+    _id: egui::Id,
+    mut env: InspectorUi<'_, '_>,
+    values: &mut [&mut dyn Reflect],
+    projector: &dyn Fn(&mut dyn Reflect) -> &mut dyn Reflect,
+) -> bool {
+    let mut current = values
+        .iter_mut()
+        .filter_map(|value| projector(&mut **value).downcast_ref::<T>().copied());
+    let Some(mut value) = current.next() else {
+        return false;
+    };
+    let mixed = current.any(|other| other != value);
+
+    let mut drag_value = float_drag_value(&mut value, options, &mut env);
+    if mixed {
+        drag_value = drag_value.custom_formatter(|_, _| "—".to_owned());
+    }
+    if !ui.add(drag_value).changed() {
+        return false;
+    }
+
+    for other in values.iter_mut() {
+        if let Some(other) = projector(&mut **other).downcast_mut::<T>() {
+            *other = value;
+        }
+    }
+    true
+}
+
+/// Registers an inspector UI for `Option<Handle<A>>` fields.
+///
+/// `Some` handles get an asset picker and a button to clear them, `None` gets a button to set a handle.
+pub fn register_option_handle_ui<A: Asset>(app: &mut App) {
+    app.register_type::<Option<Handle<A>>>();
+
+    let type_registry = app.world().resource::<AppTypeRegistry>().clone();
+    let mut type_registry = type_registry.write();
+    type_registry
+        .get_mut(TypeId::of::<Option<Handle<A>>>())
+        .unwrap()
+        .insert(InspectorEguiImpl::new(
+            option_handle_ui::<A>,
+            option_handle_ui_readonly::<A>,
+            option_handle_ui_many::<A>,
+        ));
+}
+
+fn option_handle_ui<A: Asset>(
+    value: &mut dyn Any,
+    ui: &mut egui::Ui,
+    options: &dyn Any,
+    id: egui::Id,
+    mut env: InspectorUi<'_, '_>,
+) -> bool {
+    let value = value.downcast_mut::<Option<Handle<A>>>().unwrap();
+    let mut changed = false;
+
+    ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            let Some(handle) = value else {
+                ui.label("None");
+                if ui.small_button("Set").clicked() {
+                    *value = Some(Handle::default());
+                    changed = true;
+                }
+                return;
+            };
+
+            match env.context.world.as_mut() {
+                Some(world) => match world.get_resource_mut::<Assets<A>>() {
+                    Ok(mut assets) => changed |= asset_picker(ui, id, handle, &mut assets),
+                    Err(_) => {
+                        ui.label(format!("Assets<{}> not available", A::short_type_path()));
+                    }
+                },
+                None => {
+                    ui.label(format!("{:?}", handle.id()));
+                }
+            }
+
+            if ui.small_button("✖").on_hover_text("Set to None").clicked() {
+                *value = None;
+                changed = true;
+            }
+        });
+
+        if let Some(handle) = value {
+            changed |= env.ui_for_reflect_with_options(handle, ui, id.with("handle"), options);
+        }
+    });
+
+    changed
+}
+
+fn asset_picker<A: Asset>(
+    ui: &mut egui::Ui,
+    id: egui::Id,
+    handle: &mut Handle<A>,
+    assets: &mut Assets<A>,
+) -> bool {
+    let mut picked = None;
+    egui::ComboBox::from_id_source(id.with("asset_picker"))
+        .selected_text(format!("{:?}", handle.id()))
+        .show_ui(ui, |ui| {
+            for asset_id in assets.ids() {
+                let selected = asset_id == handle.id();
+                if ui
+                    .selectable_label(selected, format!("{asset_id:?}"))
+                    .clicked()
+                {
+                    picked = Some(asset_id);
+                }
+            }
+        });
+
+    match picked.and_then(|asset_id| assets.get_strong_handle(asset_id)) {
+        Some(picked) => {
+            *handle = picked;
+            true
+        }
+        None => false,
+    }
+}
+
+fn option_handle_ui_readonly<A: Asset>(
+    value: &dyn Any,
+    ui: &mut egui::Ui,
+    _options: &dyn Any,
+    _id: egui::Id,
+    _env: InspectorUi<'_, '_>,
+) {
+    match value.downcast_ref::<Option<Handle<A>>>().unwrap() {
+        Some(handle) => ui.label(format!("{:?}", handle.id())),
+        None => ui.label("None"),
+    };
+}
+
+fn option_handle_ui_many<A: Asset>(
+    ui: &mut egui::Ui,
+    options: &dyn Any,
+    id: egui::Id,
+    env: InspectorUi<'_, '_>,
+    values: &mut [&mut dyn Reflect],
+    projector: &dyn Fn(&mut dyn Reflect) -> &mut dyn Reflect,
+) -> bool {
+    let mut current = values.iter_mut().filter_map(|value| {
+        projector(&mut **value)
+            .downcast_ref::<Option<Handle<A>>>()
+            .cloned()
+    });
+    let Some(mut value) = current.next() else {
+        return false;
+    };
+    let mixed = current.any(|other| other != value);
+
+    if mixed {
+        ui.label("—")
+            .on_hover_text("The selected entities have different values, edits apply to all");
+    }
+    if !option_handle_ui::<A>(&mut value, ui, options, id, env) {
+        return false;
+    }
+
+    for other in values.iter_mut() {
+        if let Some(other) = projector(&mut **other).downcast_mut::<Option<Handle<A>>>() {
+            other.clone_from(&value);
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs one frame of the UI, pressing `key` first
+    fn run_frame(ctx: &egui::Context, key: egui::Key, add_contents: impl FnOnce(&mut egui::Ui)) {
+        let input = egui::RawInput {
+            events: vec![egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: egui::Modifiers::NONE,
+            }],
+            ..default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, add_contents);
+        });
+    }
+
+    /// Lays out the UI with nothing focused, then focuses its first button with Tab and clicks it
+    /// with Enter
+    fn click_first_button(ctx: &egui::Context, mut add_contents: impl FnMut(&mut egui::Ui)) {
+        // the button replacing a clicked one can reuse its id and keep the focus
+        run_frame(ctx, egui::Key::Escape, &mut add_contents);
+        run_frame(ctx, egui::Key::Tab, &mut add_contents);
+        run_frame(ctx, egui::Key::Enter, &mut add_contents);
+    }
+
+    fn click_option_handle_ui(ctx: &egui::Context, value: &mut Option<Handle<Image>>) -> bool {
+        let type_registry = TypeRegistry::default();
+        let mut changed = false;
+        click_first_button(ctx, |ui| {
+            let mut context = Context::default();
+            let env = InspectorUi::new_no_short_circuit(&type_registry, &mut context);
+            changed |= option_handle_ui::<Image>(value, ui, &(), egui::Id::new("handle"), env);
+        });
+        changed
+    }
+
+    #[test]
+    fn option_handle_ui_sets_and_clears_the_handle() {
+        let ctx = egui::Context::default();
+        let mut value: Option<Handle<Image>> = None;
+
+        assert!(click_option_handle_ui(&ctx, &mut value));
+        assert_eq!(value, Some(Handle::default()));
+
+        assert!(click_option_handle_ui(&ctx, &mut value));
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn option_handle_ui_many_edits_all_values() {
+        let type_registry = TypeRegistry::default();
+        let ctx = egui::Context::default();
+        let mut a: Option<Handle<Image>> = Some(Handle::weak_from_u128(1));
+        let mut b: Option<Handle<Image>> = None;
+        let mut changed = false;
+
+        click_first_button(&ctx, |ui| {
+            let mut context = Context::default();
+            let env = InspectorUi::new_no_short_circuit(&type_registry, &mut context);
+            let mut values: [&mut dyn Reflect; 2] = [&mut a, &mut b];
+            changed |= option_handle_ui_many::<Image>(
+                ui,
+                &(),
+                egui::Id::new("handle"),
+                env,
+                &mut values,
+                &|value| value,
+            );
+        });
+
+        assert!(changed);
+        assert_eq!(a, None);
+        assert_eq!(b, None);
+    }
+
+    #[test]
+    fn pasting_applies_or_inserts_the_copied_component() {
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<Transform>();
+        let mut world = World::new();
+        let copied = Transform::from_xyz(1.0, 2.0, 3.0);
+        world.insert_resource(ComponentClipboard {
+            value: Some((TypeId::of::<Transform>(), copied.clone_value())),
+        });
+        let with = world.spawn(Transform::default()).id();
+        let without = world.spawn_empty().id();
+
+        assert_eq!(
+            paste_component(&mut world, &[with, without], &type_registry),
+            Ok(())
+        );
+        assert_eq!(world.get::<Transform>(with), Some(&copied));
+        assert_eq!(world.get::<Transform>(without), Some(&copied));
+    }
+}
@@ -8,7 +8,12 @@ pub mod debug_settings;
 pub mod diagnostics;
 pub mod gizmos;
 pub mod hierarchy;
+pub mod history;
 pub mod inspector;
+pub mod notes;
+mod reflect_capabilities;
 pub mod renderer;
 pub mod resources;
 pub mod scenes;
+pub mod undo;
+pub mod visibility;
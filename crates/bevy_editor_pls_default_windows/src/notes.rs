@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::Selection;
+use bevy_inspector_egui::bevy_inspector::guess_entity_name;
+use bevy_inspector_egui::egui;
+
+use crate::inspector::{InspectorSelection, InspectorWindow};
+
+/// A free-text note attached to an entity, e.g. for TODOs during level building.
+///
+/// The note is reflected, so it gets saved into scenes together with the entity.
+#[derive(Component, Reflect, Default, Clone)]
+#[reflect(Component, Default)]
+pub struct EntityNote(pub String);
+
+/// Lists all entities with an [`EntityNote`]
+pub struct NotesWindow;
+
+impl EditorWindow for NotesWindow {
+    type State = ();
+    const NAME: &'static str = "Notes";
+    const CATEGORY: &'static str = "Scene";
+    const ICON: Option<&'static str> = Some("🖊");
+
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let mut notes = world.query::<(Entity, &EntityNote)>();
+        let notes: Vec<_> = notes
+            .iter(world)
+            .map(|(entity, note)| (entity, note.0.clone()))
+            .collect();
+
+        if notes.is_empty() {
+            ui.label("No entities have notes");
+            return;
+        }
+
+        let inspector_state = cx.state_mut::<InspectorWindow>().unwrap();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (entity, note) in notes {
+                let name = guess_entity_name(world, entity);
+                let selected = world.resource::<Selection>().contains(entity);
+
+                let response = ui.selectable_label(selected, name).on_hover_text(&note);
+                if response.clicked() {
+                    world.resource_mut::<Selection>().select(entity);
+                    inspector_state.selected = InspectorSelection::Entities;
+                }
+
+                let first_line = note.lines().next().unwrap_or_default();
+                ui.label(egui::RichText::new(first_line).weak());
+            }
+        });
+    }
+
+    fn app_setup(app: &mut App) {
+        app.register_type::<EntityNote>();
+    }
+}
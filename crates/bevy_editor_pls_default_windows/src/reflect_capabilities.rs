@@ -0,0 +1,342 @@
+//! Which values the editor can construct through reflection.
+//!
+//! Operations needing a capability a type lacks are detected up front, instead of failing once used.
+
+use std::any::TypeId;
+use std::fmt;
+
+use bevy::ecs::reflect::ReflectFromWorld;
+use bevy::prelude::*;
+use bevy::reflect::{ReflectFromReflect, TypeInfo, TypeRegistration, TypeRegistry, VariantInfo};
+use bevy::scene::DynamicScene;
+use bevy::utils::HashSet;
+use bevy_inspector_egui::egui;
+
+pub(crate) const MISSING_DEFAULT: &str =
+    "Can't be created through reflection, it's missing #[reflect(Default)] or #[reflect(FromWorld)]";
+pub(crate) const MISSING_FROM_REFLECT: &str = "Can't be inserted from a copy through reflection, \
+    it's missing FromReflect, #[reflect(Default)] or #[reflect(FromWorld)]";
+
+/// Which of the reflected traits a type provides to construct values of it.
+///
+/// Bevy panics when e.g. inserting a reflected component which can't be turned into its concrete type,
+/// so every reflected insert in the editor checks these first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ReflectCapabilities {
+    from_reflect: bool,
+    default: bool,
+    from_world: bool,
+}
+
+impl ReflectCapabilities {
+    pub(crate) fn of(registration: &TypeRegistration) -> Self {
+        ReflectCapabilities {
+            from_reflect: registration.data::<ReflectFromReflect>().is_some(),
+            default: registration.data::<ReflectDefault>().is_some(),
+            from_world: registration.data::<ReflectFromWorld>().is_some(),
+        }
+    }
+
+    /// A value can be created from scratch, to add or reset a component
+    pub(crate) fn can_create(&self) -> bool {
+        self.default || self.from_world
+    }
+
+    /// A reflected value, like a copied component, can be turned into the concrete type to insert it
+    pub(crate) fn can_insert_reflected(&self) -> bool {
+        self.from_reflect || self.can_create()
+    }
+}
+
+/// Drops the components of the scene which can't be inserted from their reflected values,
+/// since writing the scene to the world would panic on them
+pub(crate) fn retain_insertable_components(scene: &mut DynamicScene, type_registry: &TypeRegistry) {
+    for entity in &mut scene.entities {
+        entity.components.retain(|component| {
+            let registration = component
+                .get_represented_type_info()
+                .and_then(|info| type_registry.get(info.type_id()));
+            let insertable = registration.is_some_and(|registration| {
+                ReflectCapabilities::of(registration).can_insert_reflected()
+            });
+            if !insertable {
+                warn!(
+                    "Leaving out `{}`, it can't be inserted through reflection",
+                    component.reflect_type_path()
+                );
+            }
+            insertable
+        });
+    }
+}
+
+/// A new value of the type from its reflected `Default`, or else `FromWorld`
+pub(crate) fn default_value(
+    registration: &TypeRegistration,
+    world: &mut World,
+) -> Option<Box<dyn Reflect>> {
+    if let Some(reflect_default) = registration.data::<ReflectDefault>() {
+        return Some(reflect_default.default());
+    }
+    let reflect_from_world = registration.data::<ReflectFromWorld>()?;
+    Some(reflect_from_world.from_world(world))
+}
+
+/// An edit the inspector widgets can't offer somewhere inside a type, because they construct the new
+/// values with `ReflectDefault`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Limitation {
+    /// No elements can be added to the list or map
+    AddElement {
+        collection: &'static str,
+        item: &'static str,
+    },
+    /// The enum can't be switched to the variant
+    SwitchVariant {
+        enum_type: &'static str,
+        variant: &'static str,
+        field: &'static str,
+    },
+}
+
+impl fmt::Display for Limitation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limitation::AddElement { collection, item } => write!(
+                f,
+                "Can't add elements to {collection}, {item} has no #[reflect(Default)]"
+            ),
+            Limitation::SwitchVariant {
+                enum_type,
+                variant,
+                field,
+            } => write!(
+                f,
+                "Can't switch {enum_type} to {variant}, {field} has no #[reflect(Default)]"
+            ),
+        }
+    }
+}
+
+/// The [`Limitation`]s of the type and the registered types nested in it
+pub(crate) fn structural_limitations(
+    type_registry: &TypeRegistry,
+    type_id: TypeId,
+) -> Vec<Limitation> {
+    let mut limitations = Vec::new();
+    collect_limitations(
+        type_registry,
+        type_id,
+        &mut HashSet::default(),
+        &mut limitations,
+    );
+    limitations
+}
+
+fn collect_limitations(
+    type_registry: &TypeRegistry,
+    type_id: TypeId,
+    visited: &mut HashSet<TypeId>,
+    limitations: &mut Vec<Limitation>,
+) {
+    if !visited.insert(type_id) {
+        return;
+    }
+    let Some(registration) = type_registry.get(type_id) else {
+        return;
+    };
+    let has_default = |type_id: TypeId| {
+        type_registry
+            .get_type_data::<ReflectDefault>(type_id)
+            .is_some()
+    };
+
+    let mut nested = Vec::new();
+    match registration.type_info() {
+        TypeInfo::Struct(info) => nested.extend(info.iter().map(|field| field.type_id())),
+        TypeInfo::TupleStruct(info) => nested.extend(info.iter().map(|field| field.type_id())),
+        TypeInfo::Tuple(info) => nested.extend(info.iter().map(|field| field.type_id())),
+        TypeInfo::Array(info) => nested.push(info.item_type_id()),
+        TypeInfo::List(info) => {
+            if !has_default(info.item_type_id()) {
+                limitations.push(Limitation::AddElement {
+                    collection: info.type_path(),
+                    item: info.item_type_path_table().path(),
+                });
+            }
+            nested.push(info.item_type_id());
+        }
+        TypeInfo::Map(info) => {
+            let missing = [
+                (info.key_type_id(), info.key_type_path_table().path()),
+                (info.value_type_id(), info.value_type_path_table().path()),
+            ]
+            .into_iter()
+            .find(|&(type_id, _)| !has_default(type_id));
+            if let Some((_, item)) = missing {
+                limitations.push(Limitation::AddElement {
+                    collection: info.type_path(),
+                    item,
+                });
+            }
+            nested.extend([info.key_type_id(), info.value_type_id()]);
+        }
+        TypeInfo::Enum(info) => {
+            for variant in info.iter() {
+                let fields: Vec<(TypeId, &'static str)> = match variant {
+                    VariantInfo::Struct(variant) => variant
+                        .iter()
+                        .map(|field| (field.type_id(), field.type_path()))
+                        .collect(),
+                    VariantInfo::Tuple(variant) => variant
+                        .iter()
+                        .map(|field| (field.type_id(), field.type_path()))
+                        .collect(),
+                    VariantInfo::Unit(_) => Vec::new(),
+                };
+                if let Some(&(_, field)) =
+                    fields.iter().find(|&&(type_id, _)| !has_default(type_id))
+                {
+                    limitations.push(Limitation::SwitchVariant {
+                        enum_type: info.type_path(),
+                        variant: variant.name(),
+                        field,
+                    });
+                }
+                nested.extend(fields.into_iter().map(|(type_id, _)| type_id));
+            }
+        }
+        TypeInfo::Value(_) => {}
+    }
+
+    for type_id in nested {
+        collect_limitations(type_registry, type_id, visited, limitations);
+    }
+}
+
+/// Points out the edits the inspector widgets can't offer for the components of `entity`,
+/// since they're shown without the buttons for them
+pub(crate) fn limitations_ui(
+    world: &World,
+    entity: Entity,
+    ui: &mut egui::Ui,
+    type_registry: &TypeRegistry,
+) {
+    let Some(entity) = world.get_entity(entity) else {
+        return;
+    };
+    let limitations: Vec<String> = entity
+        .archetype()
+        .components()
+        .filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+        .flat_map(|type_id| structural_limitations(type_registry, type_id))
+        .map(|limitation| limitation.to_string())
+        .collect();
+    if limitations.is_empty() {
+        return;
+    }
+    ui.label(egui::RichText::new("⚠ Some edits are unavailable").weak())
+        .on_hover_text(limitations.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Only reflects `Component`, so the editor can't construct it
+    #[derive(Component, Reflect)]
+    #[reflect(Component, from_reflect = false)]
+    struct PartiallyReflected {
+        value: u32,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component, Default)]
+    struct FullyReflected {
+        value: u32,
+    }
+
+    /// Has no `Default`, so the inspector can't add it to a list or switch an enum to hold it
+    #[derive(Reflect)]
+    struct NoDefault {
+        value: u32,
+    }
+
+    #[derive(Reflect)]
+    enum Shape {
+        Empty,
+        Filled(NoDefault),
+    }
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Nested {
+        no_default: Vec<NoDefault>,
+        full: Vec<FullyReflected>,
+        shape: Shape,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<PartiallyReflected>();
+        type_registry.register::<FullyReflected>();
+        type_registry.register::<Nested>();
+        type_registry
+    }
+
+    #[test]
+    fn detects_missing_capabilities() {
+        let type_registry = registry();
+
+        let partial = type_registry
+            .get(TypeId::of::<PartiallyReflected>())
+            .unwrap();
+        assert!(!ReflectCapabilities::of(partial).can_create());
+        assert!(!ReflectCapabilities::of(partial).can_insert_reflected());
+
+        let full = type_registry.get(TypeId::of::<FullyReflected>()).unwrap();
+        assert!(ReflectCapabilities::of(full).can_create());
+        assert!(ReflectCapabilities::of(full).can_insert_reflected());
+    }
+
+    #[test]
+    fn default_value_needs_default_or_from_world() {
+        let type_registry = registry();
+        let mut world = World::new();
+
+        let partial = type_registry
+            .get(TypeId::of::<PartiallyReflected>())
+            .unwrap();
+        assert!(default_value(partial, &mut world).is_none());
+
+        let full = type_registry.get(TypeId::of::<FullyReflected>()).unwrap();
+        let value = default_value(full, &mut world).unwrap();
+        assert_eq!(
+            value.reflect_partial_eq(&FullyReflected::default()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn finds_nested_limitations() {
+        let type_registry = registry();
+
+        let limitations = structural_limitations(&type_registry, TypeId::of::<Nested>());
+        assert_eq!(
+            limitations,
+            vec![
+                Limitation::AddElement {
+                    collection: Vec::<NoDefault>::type_path(),
+                    item: NoDefault::type_path(),
+                },
+                Limitation::SwitchVariant {
+                    enum_type: Shape::type_path(),
+                    variant: "Filled",
+                    field: NoDefault::type_path(),
+                },
+            ]
+        );
+
+        assert!(structural_limitations(&type_registry, TypeId::of::<FullyReflected>()).is_empty());
+    }
+}
@@ -1,5 +1,16 @@
-use bevy::{pbr::DirectionalLightShadowMap, prelude::*, render::renderer::RenderDevice};
-use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy::{
+    diagnostic::DiagnosticsStore,
+    pbr::{
+        wireframe::{Wireframe, WireframeConfig, WireframePlugin},
+        DirectionalLightShadowMap,
+    },
+    prelude::*,
+    render::{mesh::PrimitiveTopology, render_resource::WgpuFeatures, renderer::RenderDevice},
+};
+use bevy_editor_pls_core::{
+    editor_window::{EditorWindow, EditorWindowContext},
+    Selection,
+};
 use bevy_inspector_egui::{
     egui::{self, RichText},
     inspector_options::std_options::NumberOptions,
@@ -11,6 +22,7 @@ pub struct RendererWindow;
 impl EditorWindow for RendererWindow {
     type State = ();
     const NAME: &'static str = "Renderer";
+    const CATEGORY: &'static str = "Debug";
     const DEFAULT_SIZE: (f32, f32) = (480.0, 240.0);
 
     fn ui(world: &mut World, _: EditorWindowContext, ui: &mut egui::Ui) {
@@ -42,8 +54,25 @@ impl EditorWindow for RendererWindow {
                     directional_light_shadow_map.size = size;
                 }
                 ui.end_row();
+
+                if let Some(clear_color) = world.get_resource_mut::<ClearColor>() {
+                    ui.label("Clear color");
+                    clear_color_ui(ui, clear_color);
+                    ui.end_row();
+                }
             });
 
+            ui.heading("Statistics");
+            visible_meshes_ui(world, ui);
+            render_statistics_ui(world, ui);
+
+            ui.heading("Wireframes");
+            if features.contains(WgpuFeatures::POLYGON_MODE_LINE) {
+                wireframe_ui(world, ui);
+            } else {
+                ui.label("Enable the POLYGON_MODE_LINE feature to draw wireframes");
+            }
+
             ui.collapsing("Limits", |ui| {
                 ui.label(RichText::new(format!("{:#?}", limits)).monospace());
             });
@@ -55,4 +84,131 @@ impl EditorWindow for RendererWindow {
             });
         });
     }
+
+    fn app_setup(app: &mut App) {
+        if !app.is_plugin_added::<WireframePlugin>() {
+            app.add_plugins(WireframePlugin);
+        }
+    }
+}
+
+/// Counts the meshes visible in any view and their triangles, before batching
+fn visible_meshes_ui(world: &mut World, ui: &mut egui::Ui) {
+    let mut query = world.query::<(&Handle<Mesh>, &ViewVisibility)>();
+    let meshes = world.resource::<Assets<Mesh>>();
+
+    let (mut visible, mut triangles) = (0, 0);
+    for (mesh, visibility) in query.iter(world) {
+        if !visibility.get() {
+            continue;
+        }
+        visible += 1;
+        let Some(mesh) = meshes.get(mesh) else {
+            continue;
+        };
+        if mesh.primitive_topology() == PrimitiveTopology::TriangleList {
+            let vertices = mesh
+                .indices()
+                .map_or(mesh.count_vertices(), |indices| indices.len());
+            triangles += vertices / 3;
+        }
+    }
+
+    egui::Grid::new("visible meshes").show(ui, |ui| {
+        ui.label("Visible meshes");
+        ui.label(visible.to_string());
+        ui.end_row();
+
+        ui.label("Visible triangles");
+        ui.label(triangles.to_string());
+        ui.end_row();
+    });
+}
+
+/// Diagnostics of the render passes, recorded by bevy's `RenderDiagnosticsPlugin`
+fn render_statistics_ui(world: &World, ui: &mut egui::Ui) {
+    let render_diagnostics: Vec<_> = world
+        .get_resource::<DiagnosticsStore>()
+        .into_iter()
+        .flat_map(DiagnosticsStore::iter)
+        .filter(|diagnostic| diagnostic.path().as_str().starts_with("render/"))
+        .collect();
+
+    if render_diagnostics.is_empty() {
+        ui.label("Add the `RenderDiagnosticsPlugin` to collect render statistics");
+        return;
+    }
+
+    egui::Grid::new("render statistics").show(ui, |ui| {
+        ui.label("");
+        ui.label("Current");
+        ui.label("Average");
+        ui.end_row();
+
+        let format = |value: Option<f64>, suffix: &str| {
+            value.map_or_else(String::new, |value| format!("{value:.2}{suffix}"))
+        };
+        for diagnostic in render_diagnostics {
+            let path = diagnostic.path().as_str();
+            ui.label(path.strip_prefix("render/").unwrap_or(path));
+            ui.label(format(diagnostic.value(), &diagnostic.suffix));
+            ui.label(format(diagnostic.average(), &diagnostic.suffix));
+            ui.end_row();
+        }
+    });
+}
+
+fn clear_color_ui(ui: &mut egui::Ui, mut clear_color: Mut<ClearColor>) {
+    ui.horizontal(|ui| {
+        let srgba = clear_color.0.to_srgba();
+        let mut rgba = [srgba.red, srgba.green, srgba.blue, srgba.alpha];
+        if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+            let [red, green, blue, alpha] = rgba;
+            clear_color.0 = Color::srgba(red, green, blue, alpha);
+        }
+
+        let default = ClearColor::default().0;
+        let reset = ui.add_enabled(clear_color.0 != default, egui::Button::new("Reset"));
+        if reset.clicked() {
+            clear_color.0 = default;
+        }
+    });
+}
+
+fn wireframe_ui(world: &mut World, ui: &mut egui::Ui) {
+    egui::Grid::new("wireframes").show(ui, |ui| {
+        ui.label("Wireframe all meshes");
+        let mut config = world.get_resource_or_insert_with(WireframeConfig::default);
+        let mut global = config.global;
+        if ui.checkbox(&mut global, "").changed() {
+            config.global = global;
+        }
+        ui.end_row();
+
+        let selected: Vec<Entity> = world.resource::<Selection>().iter().collect();
+        ui.label("Wireframe selected");
+        let mut all_wireframe = !selected.is_empty()
+            && selected.iter().all(|&entity| {
+                world
+                    .get_entity(entity)
+                    .is_some_and(|entity| entity.contains::<Wireframe>())
+            });
+        let toggle = ui.add_enabled(
+            !selected.is_empty(),
+            egui::Checkbox::without_text(&mut all_wireframe),
+        );
+        if toggle.changed() {
+            for entity in selected {
+                let Some(mut entity) = world.get_entity_mut(entity) else {
+                    continue;
+                };
+                if all_wireframe {
+                    entity.insert(Wireframe);
+                } else {
+                    entity.remove::<Wireframe>();
+                }
+            }
+        }
+        ui.end_row();
+    });
 }
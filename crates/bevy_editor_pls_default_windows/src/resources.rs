@@ -1,53 +1,141 @@
+use std::any::TypeId;
+
 use bevy::{
     prelude::{AppTypeRegistry, ReflectResource, World},
     reflect::TypeRegistry,
 };
 use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
-use bevy_inspector_egui::egui;
+use bevy_inspector_egui::{bevy_inspector, egui};
 
 use crate::inspector::{InspectorSelection, InspectorWindow};
 
+#[derive(Default)]
+pub struct ResourcesWindowState {
+    /// Only list resources whose short or full type path contains this, ignoring case
+    pub filter: String,
+}
+
 pub struct ResourcesWindow;
 
 impl EditorWindow for ResourcesWindow {
-    type State = ();
+    type State = ResourcesWindowState;
 
     const NAME: &'static str = "Resources";
+    const CATEGORY: &'static str = "Debug";
 
     fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
-        let selection = &mut cx.state_mut::<InspectorWindow>().unwrap().selected;
-        let type_registry = world.resource::<AppTypeRegistry>();
+        let (state, inspector_state) = cx
+            .state_mut_pair::<ResourcesWindow, InspectorWindow>()
+            .unwrap();
+        let type_registry = world.resource::<AppTypeRegistry>().0.clone();
         let type_registry = type_registry.read();
 
-        select_resource(ui, &type_registry, selection);
+        ui.add(
+            egui::TextEdit::singleline(&mut state.filter)
+                .hint_text("Filter by type")
+                .desired_width(f32::INFINITY),
+        );
+
+        let filter = state.filter.trim().to_lowercase();
+        let resources = world_resources(world, &type_registry, &filter);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for resource in resources {
+                resource_ui(
+                    world,
+                    ui,
+                    &type_registry,
+                    resource,
+                    &mut inspector_state.selected,
+                );
+            }
+        });
     }
 }
 
-fn select_resource(
-    ui: &mut egui::Ui,
+struct ResourceEntry {
+    name: String,
+    /// `None` for resources which aren't registered with `#[reflect(Resource)]`
+    reflected: Option<TypeId>,
+}
+
+/// The resources in the world matching the filter, sorted by name
+fn world_resources(
+    world: &World,
     type_registry: &TypeRegistry,
-    selection: &mut InspectorSelection,
-) {
-    let mut resources: Vec<_> = type_registry
-        .iter()
-        .filter(|registration| registration.data::<ReflectResource>().is_some())
-        .map(|registration| {
+    filter: &str,
+) -> Vec<ResourceEntry> {
+    let mut resources: Vec<_> = world
+        .iter_resources()
+        .map(|(info, _)| {
+            let registration = info.type_id().and_then(|type_id| {
+                type_registry
+                    .get(type_id)
+                    .filter(|registration| registration.data::<ReflectResource>().is_some())
+            });
+            let name = match registration {
+                Some(registration) => registration
+                    .type_info()
+                    .type_path_table()
+                    .short_path()
+                    .to_owned(),
+                None => pretty_type_name::pretty_type_name_str(info.name()),
+            };
             (
-                registration.type_info().type_path_table().short_path(),
-                registration.type_id(),
+                info.name(),
+                name,
+                registration.map(|registration| registration.type_id()),
             )
         })
+        .filter(|(path, name, _)| {
+            filter.is_empty()
+                || name.to_lowercase().contains(filter)
+                || path.to_lowercase().contains(filter)
+        })
+        .map(|(_, name, reflected)| ResourceEntry { name, reflected })
         .collect();
-    resources.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+    resources.sort_by(|a, b| a.name.cmp(&b.name));
+    resources
+}
 
-    for (resource_name, type_id) in resources {
-        let selected = match *selection {
-            InspectorSelection::Resource(selected, _) => selected == type_id,
-            _ => false,
-        };
+/// A header selecting the resource in the inspector, which expands to edit it right here
+fn resource_ui(
+    world: &mut World,
+    ui: &mut egui::Ui,
+    type_registry: &TypeRegistry,
+    resource: ResourceEntry,
+    selection: &mut InspectorSelection,
+) {
+    let Some(type_id) = resource.reflected else {
+        ui.add_enabled(false, egui::Label::new(resource.name.as_str()))
+            .on_disabled_hover_text(
+                "Not reflectable, register it with `#[reflect(Resource)]` to edit it",
+            );
+        return;
+    };
 
-        if ui.selectable_label(selected, resource_name).clicked() {
-            *selection = InspectorSelection::Resource(type_id, resource_name.to_owned());
-        }
-    }
+    let selected = match *selection {
+        InspectorSelection::Resource(selected, _) => selected == type_id,
+        _ => false,
+    };
+
+    let id = ui.make_persistent_id(("resource", type_id));
+    egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+        .show_header(ui, |ui| {
+            if ui
+                .selectable_label(selected, resource.name.as_str())
+                .clicked()
+            {
+                *selection = InspectorSelection::Resource(type_id, resource.name.clone());
+            }
+        })
+        .body(|ui| {
+            bevy_inspector::by_type_id::ui_for_resource(
+                world,
+                type_id,
+                ui,
+                &resource.name,
+                type_registry,
+            );
+        });
 }
@@ -1,16 +1,92 @@
+use std::any::TypeId;
+use std::time::{Duration, SystemTime};
+
+use bevy::hierarchy::despawn_with_children_recursive;
 use bevy::prelude::*;
+use bevy::reflect::{ReflectMut, ReflectRef};
+use bevy::scene::{serde::SceneDeserializer, InstanceId};
+use bevy::utils::HashSet;
 use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_editor_pls_core::{Editor, Selection};
 use bevy_inspector_egui::egui::{self, RichText};
+use serde::de::DeserializeSeed;
+
+use crate::hierarchy::EditorInsertedVisibility;
+use crate::reflect_capabilities::retain_insertable_components;
 
 const DEFAULT_FILENAME: &str = "scene.scn.ron";
 
+/// Excludes the entity from scenes saved in the [`SceneWindow`], used for editor-internal entities like cameras and gizmos
 #[derive(Default, Component)]
 pub struct NotInScene;
 
+/// Marks a scene instance spawned by duplicating another instance of the same [`Scene`] handle.
+/// Unlike a deep copy, it is spawned from the scene asset, so changes to the asset show up in all instances.
+#[derive(Component)]
+pub struct LinkedSceneInstance {
+    /// The scene instance this was duplicated from
+    pub source: Entity,
+}
+
+/// Spawns another instance of the scene `entity` was spawned from, next to it in the hierarchy.
+/// Returns `None` if `entity` isn't the root of a scene instance.
+pub fn spawn_linked_scene_instance(world: &mut World, entity: Entity) -> Option<Entity> {
+    let scene = world.get::<Handle<Scene>>(entity)?.clone();
+    let transform = world.get::<Transform>(entity).copied().unwrap_or_default();
+    let name = world.get::<Name>(entity).cloned();
+    let parent = world.get::<Parent>(entity).map(Parent::get);
+    let source = world
+        .get::<LinkedSceneInstance>(entity)
+        .map_or(entity, |linked| linked.source);
+
+    let mut instance = world.spawn((
+        SceneBundle {
+            scene,
+            transform,
+            ..default()
+        },
+        LinkedSceneInstance { source },
+    ));
+    if let Some(name) = name {
+        instance.insert(name);
+    }
+    if let Some(parent) = parent {
+        instance.set_parent(parent);
+    }
+
+    Some(instance.id())
+}
+
+type SceneError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A scene file loaded from the [`SceneWindow`]
+struct LoadedScene {
+    path: String,
+    instance: InstanceId,
+    /// Modification time of the file when it was last loaded
+    modified: Option<SystemTime>,
+    /// Respawn the scene when the file changes
+    watch: bool,
+}
+
 #[derive(Default)]
 pub struct SceneWindowState {
     filename: String,
-    scene_save_result: Option<Result<(), Box<dyn std::error::Error + Send + Sync>>>,
+    /// Despawn the current scene's entities before loading a scene file
+    replace_scene: bool,
+    loaded: Vec<LoadedScene>,
+    /// Message about the last save or load
+    status: Option<Result<String, SceneError>>,
+}
+
+impl SceneWindowState {
+    fn path(&self) -> &str {
+        if self.filename.is_empty() {
+            DEFAULT_FILENAME
+        } else {
+            &self.filename
+        }
+    }
 }
 
 pub struct SceneWindow;
@@ -18,6 +94,7 @@ pub struct SceneWindow;
 impl EditorWindow for SceneWindow {
     type State = SceneWindowState;
     const NAME: &'static str = "Scenes";
+    const CATEGORY: &'static str = "Scene";
 
     fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
         let state = cx.state_mut::<SceneWindow>().unwrap();
@@ -29,27 +106,66 @@ impl EditorWindow for SceneWindow {
                 .show(ui);
 
             if res.response.changed() {
-                state.scene_save_result = None;
+                state.status = None;
             }
 
             let enter_pressed = ui.input(|input| input.key_pressed(egui::Key::Enter));
 
-            if ui.button("Save").clicked() || enter_pressed {
-                let filename = if state.filename.is_empty() {
-                    DEFAULT_FILENAME
-                } else {
-                    &state.filename
-                };
+            if ui.button("Save Scene").clicked() || enter_pressed {
+                let path = state.path().to_owned();
                 let mut query = world.query_filtered::<Entity, Without<NotInScene>>();
                 let entitys = query.iter(world).collect();
-                state.scene_save_result = Some(save_world(world, filename, entitys));
+                state.status =
+                    Some(save_world(world, &path, entitys).map(|()| format!("Saved to {path}")));
             }
         });
 
-        if let Some(status) = &state.scene_save_result {
+        ui.horizontal(|ui| {
+            if ui.button("Load Scene").clicked() {
+                let path = state.path().to_owned();
+                match load_scene(world, &path, state.replace_scene) {
+                    Ok(loaded) => {
+                        if state.replace_scene {
+                            state.loaded.clear();
+                        }
+                        state.loaded.push(loaded);
+                        state.status = Some(Ok(format!("Loaded {path}")));
+                    }
+                    Err(error) => state.status = Some(Err(error)),
+                }
+            }
+            ui.checkbox(&mut state.replace_scene, "Replace current scene");
+        });
+
+        if !state.loaded.is_empty() {
+            ui.separator();
+            ui.label("Loaded scenes");
+            for loaded in &mut state.loaded {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut loaded.watch, "Watch")
+                        .on_hover_text("Reload the scene when the file changes on disk");
+                    ui.label(&loaded.path);
+                });
+            }
+        }
+
+        let has_selection = !world.resource::<Selection>().is_empty();
+        ui.add_enabled_ui(has_selection, |ui| {
+            let export = ui
+                .button("Export Selected")
+                .on_hover_text("Save the selected entities and their descendants, like a prefab");
+            if export.clicked() {
+                let path = state.path().to_owned();
+                let entities = selected_with_descendants(world);
+                let result = save_world(world, &path, entities);
+                state.status = Some(result.map(|()| format!("Exported selection to {path}")));
+            }
+        });
+
+        if let Some(status) = &state.status {
             match status {
-                Ok(()) => {
-                    ui.label(RichText::new("Success!").color(egui::Color32::GREEN));
+                Ok(message) => {
+                    ui.label(RichText::new(message).color(egui::Color32::GREEN));
                 }
                 Err(error) => {
                     ui.label(RichText::new(error.to_string()).color(egui::Color32::RED));
@@ -57,20 +173,205 @@ impl EditorWindow for SceneWindow {
             }
         }
     }
+
+    fn app_setup(app: &mut App) {
+        app.add_systems(Update, reload_watched_scenes);
+    }
+}
+
+/// Spawns the scene file through the [`SceneSpawner`], deserializing it right away to report malformed files.
+///
+/// With `replace`, the top-level entities with a [`Transform`] that aren't [`NotInScene`] are despawned first.
+fn load_scene(world: &mut World, path: &str, replace: bool) -> Result<LoadedScene, SceneError> {
+    let modified = modified_time(path);
+    let scene = read_scene(world, path)?;
+
+    if replace {
+        let mut roots = world
+            .query_filtered::<Entity, (With<Transform>, Without<Parent>, Without<NotInScene>)>();
+        let roots: Vec<_> = roots.iter(world).collect();
+        for root in roots {
+            despawn_with_children_recursive(world, root);
+        }
+    }
+
+    let instance = spawn_scene(world, scene)?;
+    world.resource_mut::<Selection>().clear();
+
+    Ok(LoadedScene {
+        path: path.to_owned(),
+        instance,
+        modified,
+        watch: false,
+    })
+}
+
+fn read_scene(world: &World, path: &str) -> Result<DynamicScene, SceneError> {
+    let ron = std::fs::read_to_string(path)?;
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let mut deserializer = ron::de::Deserializer::from_str(&ron)?;
+    let mut scene = SceneDeserializer {
+        type_registry: &type_registry,
+    }
+    .deserialize(&mut deserializer)?;
+    retain_insertable_components(&mut scene, &type_registry);
+    Ok(scene)
+}
+
+fn spawn_scene(world: &mut World, scene: DynamicScene) -> Result<InstanceId, SceneError> {
+    let scene = world
+        .get_resource_mut::<Assets<DynamicScene>>()
+        .ok_or("the `ScenePlugin` is required to load scenes")?
+        .add(scene);
+    Ok(world.resource_mut::<SceneSpawner>().spawn_dynamic(scene))
+}
+
+fn modified_time(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// How often watched scene files are checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Respawns watched scenes whose file was modified since they were last loaded,
+/// despawning the previous instance once the new one loaded successfully.
+fn reload_watched_scenes(world: &mut World, mut since_poll: Local<Duration>) {
+    *since_poll += world.resource::<Time<Real>>().delta();
+    if *since_poll < WATCH_INTERVAL {
+        return;
+    }
+    *since_poll = Duration::ZERO;
+
+    let mut editor = world.resource_mut::<Editor>();
+    let Some(state) = editor.window_state_mut::<SceneWindow>() else {
+        return;
+    };
+    let changed: Vec<_> = state
+        .loaded
+        .iter()
+        .enumerate()
+        .filter(|(_, scene)| scene.watch)
+        .filter_map(|(i, scene)| {
+            let modified = modified_time(&scene.path);
+            (modified != scene.modified).then(|| (i, scene.path.clone(), modified))
+        })
+        .collect();
+
+    for (i, path, modified) in changed {
+        let result = read_scene(world, &path).and_then(|scene| spawn_scene(world, scene));
+
+        let mut editor = world.resource_mut::<Editor>();
+        let state = editor.window_state_mut::<SceneWindow>().unwrap();
+        let loaded = &mut state.loaded[i];
+        // also remembered for files which failed to load, so they are only retried once they change again
+        loaded.modified = modified;
+        match result {
+            Ok(instance) => {
+                let previous = std::mem::replace(&mut loaded.instance, instance);
+                state.status = Some(Ok(format!("Reloaded {path}")));
+                world
+                    .resource_mut::<SceneSpawner>()
+                    .despawn_instance(previous);
+            }
+            Err(error) => state.status = Some(Err(error)),
+        }
+    }
+}
+
+fn selected_with_descendants(world: &World) -> std::collections::HashSet<Entity> {
+    let mut entities = std::collections::HashSet::new();
+    let mut stack: Vec<Entity> = world.resource::<Selection>().iter().collect();
+    while let Some(entity) = stack.pop() {
+        let Some(entity_ref) = world.get_entity(entity) else {
+            continue;
+        };
+        if entity_ref.contains::<NotInScene>() || !entities.insert(entity) {
+            continue;
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter().copied());
+        }
+    }
+    entities
 }
 
 fn save_world(
     world: &World,
     name: &str,
     entities: std::collections::HashSet<Entity>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<(), SceneError> {
     let type_registry_arc = world.get_resource::<AppTypeRegistry>().unwrap();
     let type_registry = type_registry_arc.read();
     let mut scene_builder = DynamicSceneBuilder::from_world(world);
     scene_builder = scene_builder.extract_entities(entities.into_iter());
-    let scene = scene_builder.build();
+    let mut scene = scene_builder.build();
+
+    // visibility only toggled in the editor isn't part of the scene
+    for entity in &mut scene.entities {
+        if world
+            .get::<EditorInsertedVisibility>(entity.entity)
+            .is_some()
+        {
+            entity.components.retain(|component| {
+                component
+                    .get_represented_type_info()
+                    .is_none_or(|info| info.type_id() != TypeId::of::<Visibility>())
+            });
+        }
+    }
+
+    drop_references_outside_scene(&mut scene);
 
     let ron = scene.serialize(&type_registry)?;
     std::fs::write(name, ron)?;
     Ok(())
 }
+
+/// Removes `Children` entries and `Parent`s pointing to entities which aren't part of the scene,
+/// so that loading it doesn't reference missing entities.
+pub(crate) fn drop_references_outside_scene(scene: &mut DynamicScene) {
+    let in_scene: HashSet<Entity> = scene.entities.iter().map(|entity| entity.entity).collect();
+    let is_type = |component: &dyn Reflect, type_id: TypeId| {
+        component
+            .get_represented_type_info()
+            .is_some_and(|info| info.type_id() == type_id)
+    };
+
+    for entity in &mut scene.entities {
+        entity.components.retain(|component| {
+            if !is_type(&**component, TypeId::of::<Parent>()) {
+                return true;
+            }
+            let parent = match component.reflect_ref() {
+                ReflectRef::TupleStruct(parent) => parent.field(0),
+                _ => None,
+            };
+            parent
+                .and_then(|parent| parent.downcast_ref::<Entity>())
+                .is_none_or(|parent| in_scene.contains(parent))
+        });
+
+        for component in &mut entity.components {
+            if !is_type(&**component, TypeId::of::<Children>()) {
+                continue;
+            }
+            let ReflectMut::TupleStruct(children) = component.reflect_mut() else {
+                continue;
+            };
+            let Some(ReflectMut::List(children)) = children.field_mut(0).map(Reflect::reflect_mut)
+            else {
+                continue;
+            };
+            for i in (0..children.len()).rev() {
+                let child = children
+                    .get(i)
+                    .and_then(|child| child.downcast_ref::<Entity>());
+                if child.is_some_and(|child| !in_scene.contains(child)) {
+                    children.remove(i);
+                }
+            }
+        }
+    }
+}
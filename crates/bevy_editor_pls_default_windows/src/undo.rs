@@ -0,0 +1,310 @@
+//! Undoable editor commands for the default windows, recorded in the [`UndoStack`]
+
+use bevy::ecs::entity::EntityHashMap;
+use bevy::hierarchy::despawn_with_children_recursive;
+use bevy::prelude::*;
+use bevy_editor_pls_core::{EditorCommand, UndoStack};
+
+use crate::reflect_capabilities::retain_insertable_components;
+use crate::scenes::{drop_references_outside_scene, NotInScene};
+
+/// Transforms changed in one go, like by dragging the transform gizmo
+pub struct TransformEdit {
+    /// Each entity with its transform before and after the edit
+    edits: Vec<(Entity, Transform, Transform)>,
+    description: String,
+}
+
+impl TransformEdit {
+    pub fn new(world: &World, edits: Vec<(Entity, Transform, Transform)>) -> Self {
+        let entities: Vec<_> = edits.iter().map(|&(entity, ..)| entity).collect();
+        let description = format!("Transform {}", entities_name(world, &entities));
+        TransformEdit { edits, description }
+    }
+}
+
+impl EditorCommand for TransformEdit {
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn undo(&mut self, world: &mut World) {
+        for &(entity, before, _) in &self.edits {
+            if let Some(mut transform) = world.get_mut::<Transform>(entity) {
+                *transform = before;
+            }
+        }
+    }
+
+    fn redo(&mut self, world: &mut World) {
+        for &(entity, _, after) in &self.edits {
+            if let Some(mut transform) = world.get_mut::<Transform>(entity) {
+                *transform = after;
+            }
+        }
+    }
+
+    fn map_entities(&mut self, entity_map: &EntityHashMap<Entity>) {
+        for (entity, ..) in &mut self.edits {
+            *entity = entity_map.get(entity).copied().unwrap_or(*entity);
+        }
+    }
+}
+
+/// Despawns the entities with their descendants, and records it in the [`UndoStack`]
+pub fn delete_with_undo(world: &mut World, entities: &[Entity]) {
+    let mut tree = EntityTree::new(world, entities);
+    if tree.roots.is_empty() {
+        return;
+    }
+    tree.despawn(world);
+    world.resource_mut::<UndoStack>().push(DeleteEntities(tree));
+}
+
+/// Records entities which were just spawned, like duplicates, so undoing despawns them again.
+///
+/// `action` describes how they were spawned, like `Duplicate`.
+pub fn record_spawned(world: &mut World, entities: &[Entity], action: &'static str) {
+    let tree = EntityTree::new(world, entities);
+    if !tree.roots.is_empty() {
+        world
+            .resource_mut::<UndoStack>()
+            .push(SpawnEntities { tree, action });
+    }
+}
+
+/// The name of a single entity, or how many there are
+fn entities_name(world: &World, entities: &[Entity]) -> String {
+    match entities {
+        [entity] => world
+            .get::<Name>(*entity)
+            .map_or_else(|| entity.to_string(), |name| name.to_string()),
+        entities => format!("{} entities", entities.len()),
+    }
+}
+
+/// Undoing respawns the deleted entities from their reflected components
+pub struct DeleteEntities(EntityTree);
+
+impl EditorCommand for DeleteEntities {
+    fn description(&self) -> String {
+        format!("Delete {}", self.0.name)
+    }
+    fn undo(&mut self, world: &mut World) {
+        self.0.respawn(world);
+    }
+    fn redo(&mut self, world: &mut World) {
+        self.0.despawn(world);
+    }
+    fn respawned_entities(&self) -> EntityHashMap<Entity> {
+        self.0.respawned.clone()
+    }
+    fn map_entities(&mut self, entity_map: &EntityHashMap<Entity>) {
+        self.0.map_entities(entity_map);
+    }
+}
+
+/// Undoing despawns the spawned entities, redoing respawns them from their reflected components
+pub struct SpawnEntities {
+    tree: EntityTree,
+    action: &'static str,
+}
+
+impl EditorCommand for SpawnEntities {
+    fn description(&self) -> String {
+        format!("{} {}", self.action, self.tree.name)
+    }
+    fn undo(&mut self, world: &mut World) {
+        self.tree.despawn(world);
+    }
+    fn redo(&mut self, world: &mut World) {
+        self.tree.respawn(world);
+    }
+    fn respawned_entities(&self) -> EntityHashMap<Entity> {
+        self.tree.respawned.clone()
+    }
+    fn map_entities(&mut self, entity_map: &EntityHashMap<Entity>) {
+        self.tree.map_entities(entity_map);
+    }
+}
+
+/// Entities with all their descendants, which can be despawned and respawned again.
+///
+/// Only components registered with `#[reflect(Component)]` which can be inserted through reflection
+/// survive being respawned. Editor-internal descendants marked [`NotInScene`], like gizmo markers,
+/// aren't respawned, the editor adds them again itself.
+struct EntityTree {
+    /// The top-most entities, with the parent they are attached to
+    roots: Vec<(Entity, Option<Entity>)>,
+    /// For the description of the command
+    name: String,
+    /// The entities while they are despawned
+    scene: Option<DynamicScene>,
+    /// New ids of entities whose id was taken by another entity when they were respawned
+    respawned: EntityHashMap<Entity>,
+}
+
+impl EntityTree {
+    /// Entities which are descendants of other listed entities are part of their tree
+    fn new(world: &World, entities: &[Entity]) -> Self {
+        let is_descendant_of_listed = |entity: Entity| {
+            std::iter::successors(world.get::<Parent>(entity), |parent| {
+                world.get::<Parent>(parent.get())
+            })
+            .any(|parent| entities.contains(&parent.get()))
+        };
+        let roots: Vec<_> = entities
+            .iter()
+            .copied()
+            .filter(|&entity| world.get_entity(entity).is_some())
+            .filter(|&entity| !is_descendant_of_listed(entity))
+            .map(|entity| (entity, world.get::<Parent>(entity).map(Parent::get)))
+            .collect();
+        let root_entities: Vec<_> = roots.iter().map(|&(root, _)| root).collect();
+        let name = entities_name(world, &root_entities);
+
+        EntityTree {
+            roots,
+            name,
+            scene: None,
+            respawned: EntityHashMap::default(),
+        }
+    }
+
+    fn despawn(&mut self, world: &mut World) {
+        for (root, parent) in &mut self.roots {
+            *parent = world.get::<Parent>(*root).map(Parent::get);
+        }
+
+        let mut entities = Vec::new();
+        let mut stack: Vec<Entity> = self.roots.iter().map(|&(root, _)| root).collect();
+        while let Some(entity) = stack.pop() {
+            if world
+                .get_entity(entity)
+                .is_none_or(|entity| entity.contains::<NotInScene>())
+            {
+                continue;
+            }
+            entities.push(entity);
+            if let Some(children) = world.get::<Children>(entity) {
+                stack.extend(children.iter().copied());
+            }
+        }
+
+        let mut scene = DynamicSceneBuilder::from_world(world)
+            .extract_entities(entities.into_iter())
+            .build();
+        // the roots are attached to their parents again after respawning
+        drop_references_outside_scene(&mut scene);
+        retain_insertable_components(&mut scene, &world.resource::<AppTypeRegistry>().read());
+        self.scene = Some(scene);
+
+        for &(root, _) in &self.roots {
+            if world.get_entity(root).is_some() {
+                despawn_with_children_recursive(world, root);
+            }
+        }
+    }
+
+    /// Respawns the entities under their previous ids where those are still free
+    fn respawn(&mut self, world: &mut World) {
+        let Some(scene) = self.scene.take() else {
+            return;
+        };
+
+        // new ids are only picked once all free previous ids are taken, so they don't collide
+        let mut entity_map = EntityHashMap::default();
+        for scene_entity in &scene.entities {
+            if let Some(entity) = world.get_or_spawn(scene_entity.entity) {
+                entity_map.insert(scene_entity.entity, entity.id());
+            }
+        }
+        for scene_entity in &scene.entities {
+            if !entity_map.contains_key(&scene_entity.entity) {
+                entity_map.insert(scene_entity.entity, world.spawn_empty().id());
+            }
+        }
+        if let Err(error) = scene.write_to_world(world, &mut entity_map) {
+            warn!("Failed to respawn entities: {error}");
+        }
+
+        self.respawned = entity_map
+            .into_iter()
+            .filter(|(previous, entity)| previous != entity)
+            .collect();
+        let respawned = self.respawned.clone();
+        self.map_entities(&respawned);
+
+        for &(root, parent) in &self.roots {
+            let parent = parent.filter(|&parent| world.get_entity(parent).is_some());
+            if let Some(parent) = parent {
+                world.entity_mut(parent).add_child(root);
+            }
+        }
+    }
+
+    fn map_entities(&mut self, entity_map: &EntityHashMap<Entity>) {
+        let map = |entity: Entity| entity_map.get(&entity).copied().unwrap_or(entity);
+        for (root, parent) in &mut self.roots {
+            *root = map(*root);
+            *parent = parent.map(map);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_editor_pls_core::Selection;
+
+    use super::*;
+
+    /// Only reflects `Component`, so it can't be respawned from its reflected value
+    #[derive(Component, Reflect)]
+    #[reflect(Component, from_reflect = false)]
+    struct PartiallyReflected;
+
+    #[test]
+    fn undoing_a_deleted_light_leaves_out_its_gizmo_marker() {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Name>();
+            type_registry.register::<Transform>();
+            type_registry.register::<PointLight>();
+            type_registry.register::<Parent>();
+            type_registry.register::<Children>();
+            type_registry.register::<PartiallyReflected>();
+        }
+        world.insert_resource(type_registry);
+        world.init_resource::<UndoStack>();
+        world.init_resource::<Selection>();
+
+        let light = world
+            .spawn((
+                Name::new("Light"),
+                PointLight::default(),
+                Transform::default(),
+                PartiallyReflected,
+            ))
+            .with_children(|children| {
+                children.spawn((Name::new("PointLight Gizmo"), NotInScene));
+            })
+            .id();
+
+        delete_with_undo(&mut world, &[light]);
+        assert!(world.get_entity(light).is_none());
+        assert_eq!(world.query::<&NotInScene>().iter(&world).count(), 0);
+
+        assert!(UndoStack::undo(&mut world));
+        let light = world.entity(light);
+        assert_eq!(light.get::<Name>().unwrap().as_str(), "Light");
+        assert!(light.contains::<PointLight>());
+        assert!(!light.contains::<PartiallyReflected>());
+        assert_eq!(
+            light.get::<Children>().map_or(0, |children| children.len()),
+            0
+        );
+        assert_eq!(world.query::<&NotInScene>().iter(&world).count(), 0);
+    }
+}
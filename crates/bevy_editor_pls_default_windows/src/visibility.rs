@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_editor_pls_core::editor_window::{EditorWindow, EditorWindowContext};
+use bevy_inspector_egui::egui;
+
+use crate::hierarchy::HideInEditor;
+
+/// A named set of entities whose visibility can be toggled at once
+pub struct VisibilityGroup {
+    name: Cow<'static, str>,
+    entities: fn(&mut World) -> Vec<Entity>,
+}
+
+impl VisibilityGroup {
+    pub fn new(name: Cow<'static, str>, entities: fn(&mut World) -> Vec<Entity>) -> Self {
+        VisibilityGroup { name, entities }
+    }
+
+    /// All entities with the component `T`
+    pub fn with_component<T: Component>(name: Cow<'static, str>) -> Self {
+        VisibilityGroup::new(name, |world| {
+            world
+                .query_filtered::<Entity, (With<T>, Without<HideInEditor>)>()
+                .iter(world)
+                .collect()
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+pub struct VisibilityWindowState {
+    groups: Vec<VisibilityGroup>,
+    /// Entities hidden by each currently hidden group
+    hidden_groups: HashMap<Cow<'static, str>, Vec<Entity>>,
+    /// The visibility entities had before they were hidden by any group
+    previous_visibility: HashMap<Entity, Visibility>,
+}
+
+impl VisibilityWindowState {
+    pub fn add_group(&mut self, group: VisibilityGroup) {
+        self.groups.push(group);
+    }
+
+    pub fn groups(&self) -> &[VisibilityGroup] {
+        &self.groups
+    }
+
+    pub fn is_hidden(&self, name: &str) -> bool {
+        self.hidden_groups.contains_key(name)
+    }
+
+    pub fn hidden_groups(&self) -> impl Iterator<Item = &str> {
+        self.hidden_groups.keys().map(|name| name.as_ref())
+    }
+
+    /// Hides every entity of the group, remembering their previous visibility
+    pub fn hide_group(&mut self, world: &mut World, name: &str) {
+        let Some(group) = self.groups.iter().find(|group| group.name == name) else {
+            warn!("no visibility group named `{name}`");
+            return;
+        };
+        if self.hidden_groups.contains_key(name) {
+            return;
+        }
+
+        let mut hidden = Vec::new();
+        for entity in (group.entities)(world) {
+            let Some(mut visibility) = world.get_mut::<Visibility>(entity) else {
+                continue;
+            };
+            self.previous_visibility
+                .entry(entity)
+                .or_insert(*visibility);
+            *visibility = Visibility::Hidden;
+            hidden.push(entity);
+        }
+        self.hidden_groups.insert(group.name.clone(), hidden);
+    }
+
+    /// Restores the visibility of the group's entities, unless they are still hidden by another group
+    pub fn show_group(&mut self, world: &mut World, name: &str) {
+        let Some(hidden) = self.hidden_groups.remove(name) else {
+            return;
+        };
+
+        for entity in hidden {
+            let still_hidden = self
+                .hidden_groups
+                .values()
+                .any(|entities| entities.contains(&entity));
+            if still_hidden {
+                continue;
+            }
+
+            let Some(previous) = self.previous_visibility.remove(&entity) else {
+                continue;
+            };
+            if let Some(mut visibility) = world.get_mut::<Visibility>(entity) {
+                *visibility = previous;
+            }
+        }
+    }
+
+    /// Restores every group. Entities hidden individually stay hidden.
+    pub fn show_all(&mut self, world: &mut World) {
+        let hidden: Vec<_> = self.hidden_groups.keys().cloned().collect();
+        for name in hidden {
+            self.show_group(world, &name);
+        }
+    }
+
+    fn set_group_hidden(&mut self, world: &mut World, name: &str, hidden: bool) {
+        if hidden {
+            self.hide_group(world, name);
+        } else {
+            self.show_group(world, name);
+        }
+    }
+}
+
+impl Default for VisibilityWindowState {
+    fn default() -> Self {
+        let mut state = VisibilityWindowState {
+            groups: Vec::new(),
+            hidden_groups: HashMap::default(),
+            previous_visibility: HashMap::default(),
+        };
+
+        state.add_group(VisibilityGroup::new("Lights".into(), |world| {
+            let mut lights = Vec::new();
+            lights.extend(
+                world
+                    .query_filtered::<Entity, (With<PointLight>, Without<HideInEditor>)>()
+                    .iter(world),
+            );
+            lights.extend(
+                world
+                    .query_filtered::<Entity, (With<SpotLight>, Without<HideInEditor>)>()
+                    .iter(world),
+            );
+            lights.extend(
+                world
+                    .query_filtered::<Entity, (With<DirectionalLight>, Without<HideInEditor>)>()
+                    .iter(world),
+            );
+            lights
+        }));
+        state.add_group(VisibilityGroup::with_component::<Handle<Mesh>>(
+            "Meshes".into(),
+        ));
+        state.add_group(VisibilityGroup::with_component::<Sprite>("Sprites".into()));
+        state.add_group(VisibilityGroup::with_component::<Node>("UI".into()));
+
+        state
+    }
+}
+
+pub struct VisibilityWindow;
+
+impl EditorWindow for VisibilityWindow {
+    type State = VisibilityWindowState;
+    const NAME: &'static str = "Visibility";
+    const CATEGORY: &'static str = "Scene";
+
+    fn ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let state = cx.state_mut::<VisibilityWindow>().unwrap();
+        visibility_groups_ui(world, state, ui);
+    }
+
+    fn viewport_toolbar_ui(world: &mut World, mut cx: EditorWindowContext, ui: &mut egui::Ui) {
+        let state = cx.state_mut::<VisibilityWindow>().unwrap();
+        ui.menu_button("👁", |ui| visibility_groups_ui(world, state, ui))
+            .response
+            .on_hover_text("Hide or show groups of entities");
+    }
+}
+
+fn visibility_groups_ui(world: &mut World, state: &mut VisibilityWindowState, ui: &mut egui::Ui) {
+    let mut toggled = None;
+    for group in state.groups() {
+        let mut visible = !state.is_hidden(group.name());
+        if ui.checkbox(&mut visible, group.name()).changed() {
+            toggled = Some((group.name.clone(), !visible));
+        }
+    }
+    if let Some((name, hidden)) = toggled {
+        state.set_group_hidden(world, &name, hidden);
+    }
+
+    ui.separator();
+    if ui.button("Show all").clicked() {
+        state.show_all(world);
+        ui.close_menu();
+    }
+}